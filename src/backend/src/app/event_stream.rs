@@ -4,23 +4,39 @@ use tracing::warn;
 
 use crate::config::Config;
 use crate::syncthing_client::SyncthingClient;
+use crate::utils::backoff::Backoff;
 
-use super::protocol::{EVENT_HEARTBEAT_SECS, EVENT_RECONNECT_DELAY_SECS, EVENT_STREAM_TIMEOUT_SECS};
+use super::protocol::{
+    EVENT_HEARTBEAT_SECS, EVENT_RECONNECT_DELAY_SECS, EVENT_RECONNECT_MAX_DELAY_SECS,
+    EVENT_STREAM_TIMEOUT_SECS,
+};
 use super::Backend;
 
 /// Drives the Syncthing event stream and orchestrates when to send status updates.
 ///
 /// This implements:
-/// - Event polling with automatic reconnection
+/// - Event polling with automatic reconnection, backing off exponentially
+///   with jitter while Syncthing stays unreachable
 /// - Heartbeat timing (send status even without events)
 /// - Backend notification decisions
+/// - Completion tracking for rescans requested from the UI, given up when
+///   events were dropped or the stream reconnects
+/// - Feeding events into the backend client's cached status model, and
+///   invalidating it after a reconnect or when Syncthing dropped events
+/// - Recording file changes from the event stream and `/rest/events/disk`
+///   in the change journal
 pub async fn drive_syncthing_stream(
     functionality: BackendReplier<Backend>,
     config: Config,
 ) {
     let mut client: Option<SyncthingClient> = None;
     let mut last_event_id: u64 = 0;
+    let mut last_disk_event_id: u64 = 0;
     let mut last_emit = Instant::now() - Duration::from_secs(EVENT_HEARTBEAT_SECS);
+    let mut backoff = Backoff::new(
+        Duration::from_secs(EVENT_RECONNECT_DELAY_SECS),
+        Duration::from_secs(EVENT_RECONNECT_MAX_DELAY_SECS),
+    );
 
     loop {
         // Ensure client is connected
@@ -29,10 +45,15 @@ pub async fn drive_syncthing_stream(
                 Ok(new_client) => {
                     client = Some(new_client);
                     last_event_id = 0;
+                    last_disk_event_id = 0;
+                    let mut backend = functionality.backend.lock().await;
+                    if let Some(backend_client) = backend.client.as_mut() {
+                        backend_client.invalidate_model();
+                    }
                 }
                 Err(err) => {
                     warn!(error = ?err, "Failed to connect to Syncthing");
-                    sleep(Duration::from_secs(EVENT_RECONNECT_DELAY_SECS)).await;
+                    sleep(backoff.next_delay()).await;
                     continue;
                 }
             }
@@ -48,8 +69,56 @@ pub async fn drive_syncthing_stream(
 
         match wait_result {
             Ok(result) => {
+                backoff.reset();
                 last_event_id = result.last_event_id;
                 let has_events = result.has_updates;
+                let finished_scans: Vec<String> = result
+                    .events
+                    .iter()
+                    .filter_map(|event| event.finished_scan_folder())
+                    .map(|folder| folder.to_string())
+                    .collect();
+
+                let disk_events = match client
+                    .as_mut()
+                    .expect("client initialized")
+                    .disk_changes(last_disk_event_id)
+                    .await
+                {
+                    Ok(disk_result) => {
+                        last_disk_event_id = disk_result.last_event_id;
+                        if disk_result.gap {
+                            warn!("Dropped disk change events, the journal may be incomplete");
+                        }
+                        disk_result.events
+                    }
+                    Err(err) => {
+                        warn!(error = ?err, "Failed to fetch disk change events");
+                        Vec::new()
+                    }
+                };
+                if !disk_events.is_empty() {
+                    let mut backend = functionality.backend.lock().await;
+                    backend.journal.record(&disk_events).await;
+                }
+
+                if has_events {
+                    let mut backend = functionality.backend.lock().await;
+                    if let Some(backend_client) = backend.client.as_mut() {
+                        if result.gap {
+                            warn!("Syncthing events were dropped, refetching the status model");
+                            backend_client.invalidate_model();
+                        }
+                        backend_client.apply_events(&result.events);
+                    }
+                    backend.journal.record(&result.events).await;
+                    if !finished_scans.is_empty() {
+                        backend.complete_rescans(&functionality, &finished_scans);
+                    }
+                    if result.gap {
+                        backend.abandon_rescans(&functionality, "Syncthing events were dropped");
+                    }
+                }
                 let heartbeat_due = last_emit.elapsed().as_secs() >= EVENT_HEARTBEAT_SECS;
 
                 if has_events || heartbeat_due {
@@ -67,7 +136,15 @@ pub async fn drive_syncthing_stream(
             Err(err) => {
                 warn!(error = ?err, "Syncthing event polling error");
                 client = None; // Force reconnection
-                sleep(Duration::from_secs(EVENT_RECONNECT_DELAY_SECS)).await;
+                {
+                    let mut backend = functionality.backend.lock().await;
+                    // Scans finishing while disconnected are never reported
+                    backend.abandon_rescans(&functionality, "lost connection to Syncthing");
+                    backend
+                        .send_status(&functionality, "syncthing-reconnecting")
+                        .await;
+                }
+                sleep(backoff.next_delay()).await;
             }
         }
     }
@@ -2,24 +2,35 @@ mod event_stream;
 mod operations;
 pub mod protocol;
 mod realtime;
+mod scheduler;
 mod status_builder;
 
-pub use protocol::{ControlRequest, GuiAddressToggleRequest};
+pub use protocol::{
+    BandwidthSetRequest, ChangesQueryRequest, ConflictResolveRequest, ConflictScanRequest,
+    ControlRequest, DeviceQrRequest, FolderCreateRequest, FolderDeleteRequest, FolderPauseRequest,
+    FolderUpdateRequest, GuiAddressToggleRequest, GuiAuthSetRequest, IgnoresGetRequest,
+    IgnoresSetRequest, PeerPauseRequest, PendingDeviceRequest, PendingFolderRequest, RescanRequest,
+    VersionRestoreRequest, VersionsListRequest,
+};
+
+use std::collections::HashSet;
 
 use appload_client::{AppLoadBackend, BackendReplier, Message};
 use async_trait::async_trait;
 use serde_json::json;
 use tokio::task::JoinHandle;
-use tracing::error;
+use tracing::{error, warn};
 
 use crate::config::Config;
 use crate::deployment::{Installer, Updater};
-use crate::syncthing_client::SyncthingClient;
+use crate::scheduler::PolicyStatus;
+use crate::syncthing_client::{ChangeJournal, ConnectionTracker, SyncthingClient};
 
 use self::protocol::*;
 
 pub struct Backend {
     pub client: Option<SyncthingClient>,
+    pub connection: ConnectionTracker,
     pub config: Config,
     pub installer: Installer,
     pub install_in_progress: bool,
@@ -32,8 +43,12 @@ pub struct Backend {
     pub pending_update_url: Option<String>,
     pub update_pending_restart: bool,
     pub update_restart_seconds_remaining: Option<u32>,
+    pub pending_rescans: HashSet<String>,
+    pub journal: ChangeJournal,
+    pub policy_status: PolicyStatus,
     pub realtime_task: Option<JoinHandle<()>>,
     pub systemd_monitor_task: Option<JoinHandle<()>>,
+    pub scheduler_task: Option<JoinHandle<()>>,
 }
 
 impl Backend {
@@ -41,8 +56,13 @@ impl Backend {
         let client = SyncthingClient::discover(&config).await.ok();
         let installer = Installer::new(config.clone());
         let updater = Updater::new();
+        let journal_path = Config::change_journal_path()
+            .map_err(|err| warn!(error = ?err, "Change journal will not be persisted"))
+            .ok();
+        let journal = ChangeJournal::load(journal_path).await;
         Self {
             client,
+            connection: ConnectionTracker::default(),
             config,
             installer,
             install_in_progress: false,
@@ -55,14 +75,24 @@ impl Backend {
             pending_update_url: None,
             update_pending_restart: false,
             update_restart_seconds_remaining: None,
+            pending_rescans: HashSet::new(),
+            journal,
+            policy_status: PolicyStatus::default(),
             realtime_task: None,
             systemd_monitor_task: None,
+            scheduler_task: None,
         }
     }
 
     pub async fn send_status(&mut self, functionality: &BackendReplier<Self>, reason: &str) {
-        let snapshot =
-            status_builder::build_status_payload(&self.config, &mut self.client, reason).await;
+        let snapshot = status_builder::build_status_payload(
+            &self.config,
+            &mut self.client,
+            &mut self.connection,
+            self.policy_status.clone(),
+            reason,
+        )
+        .await;
         match serde_json::to_string(&snapshot) {
             Ok(payload) => {
                 if let Err(err) = functionality.send_message(MSG_STATUS_UPDATE, &payload) {
@@ -124,6 +154,173 @@ impl AppLoadBackend for Backend {
                     ),
                 }
             }
+            MSG_GUI_AUTH_SET_REQUEST => {
+                match serde_json::from_str::<GuiAuthSetRequest>(&message.contents) {
+                    Ok(req) => self.handle_gui_auth_set(functionality, req).await,
+                    Err(err) => {
+                        self.send_error(functionality, &format!("Invalid GUI auth payload: {err}"))
+                    }
+                }
+            }
+            MSG_FOLDER_PAUSE_REQUEST => {
+                match serde_json::from_str::<FolderPauseRequest>(&message.contents) {
+                    Ok(req) => self.handle_folder_pause(functionality, req).await,
+                    Err(err) => self.send_error(
+                        functionality,
+                        &format!("Invalid folder pause payload: {err}"),
+                    ),
+                }
+            }
+            MSG_PEER_PAUSE_REQUEST => {
+                match serde_json::from_str::<PeerPauseRequest>(&message.contents) {
+                    Ok(req) => self.handle_peer_pause(functionality, req).await,
+                    Err(err) => self.send_error(
+                        functionality,
+                        &format!("Invalid peer pause payload: {err}"),
+                    ),
+                }
+            }
+            MSG_RESCAN_REQUEST => {
+                match serde_json::from_str::<RescanRequest>(&message.contents) {
+                    Ok(req) => self.handle_rescan(functionality, req).await,
+                    Err(err) => {
+                        self.send_error(functionality, &format!("Invalid rescan payload: {err}"))
+                    }
+                }
+            }
+            MSG_PENDING_DEVICE_REQUEST => {
+                match serde_json::from_str::<PendingDeviceRequest>(&message.contents) {
+                    Ok(req) => self.handle_pending_device(functionality, req).await,
+                    Err(err) => self.send_error(
+                        functionality,
+                        &format!("Invalid pending device payload: {err}"),
+                    ),
+                }
+            }
+            MSG_PENDING_FOLDER_REQUEST => {
+                match serde_json::from_str::<PendingFolderRequest>(&message.contents) {
+                    Ok(req) => self.handle_pending_folder(functionality, req).await,
+                    Err(err) => self.send_error(
+                        functionality,
+                        &format!("Invalid pending folder payload: {err}"),
+                    ),
+                }
+            }
+            MSG_FOLDER_CREATE_REQUEST => {
+                match serde_json::from_str::<FolderCreateRequest>(&message.contents) {
+                    Ok(req) => self.handle_folder_create(functionality, req).await,
+                    Err(err) => self.send_error(
+                        functionality,
+                        &format!("Invalid folder create payload: {err}"),
+                    ),
+                }
+            }
+            MSG_FOLDER_UPDATE_REQUEST => {
+                match serde_json::from_str::<FolderUpdateRequest>(&message.contents) {
+                    Ok(req) => self.handle_folder_update(functionality, req).await,
+                    Err(err) => self.send_error(
+                        functionality,
+                        &format!("Invalid folder update payload: {err}"),
+                    ),
+                }
+            }
+            MSG_FOLDER_DELETE_REQUEST => {
+                match serde_json::from_str::<FolderDeleteRequest>(&message.contents) {
+                    Ok(req) => self.handle_folder_delete(functionality, req).await,
+                    Err(err) => self.send_error(
+                        functionality,
+                        &format!("Invalid folder delete payload: {err}"),
+                    ),
+                }
+            }
+            MSG_DEVICE_QR_REQUEST => {
+                match serde_json::from_str::<DeviceQrRequest>(&message.contents) {
+                    Ok(req) => self.handle_device_qr(functionality, req).await,
+                    Err(err) => self.send_error(
+                        functionality,
+                        &format!("Invalid device QR payload: {err}"),
+                    ),
+                }
+            }
+            MSG_CONFLICT_SCAN_REQUEST => {
+                match serde_json::from_str::<ConflictScanRequest>(&message.contents) {
+                    Ok(req) => self.handle_conflict_scan(functionality, req).await,
+                    Err(err) => self.send_error(
+                        functionality,
+                        &format!("Invalid conflict scan payload: {err}"),
+                    ),
+                }
+            }
+            MSG_CONFLICT_RESOLVE_REQUEST => {
+                match serde_json::from_str::<ConflictResolveRequest>(&message.contents) {
+                    Ok(req) => self.handle_conflict_resolve(functionality, req).await,
+                    Err(err) => self.send_error(
+                        functionality,
+                        &format!("Invalid conflict resolve payload: {err}"),
+                    ),
+                }
+            }
+            MSG_VERSIONS_LIST_REQUEST => {
+                match serde_json::from_str::<VersionsListRequest>(&message.contents) {
+                    Ok(req) => self.handle_versions_list(functionality, req).await,
+                    Err(err) => self.send_error(
+                        functionality,
+                        &format!("Invalid versions list payload: {err}"),
+                    ),
+                }
+            }
+            MSG_VERSION_RESTORE_REQUEST => {
+                match serde_json::from_str::<VersionRestoreRequest>(&message.contents) {
+                    Ok(req) => self.handle_version_restore(functionality, req).await,
+                    Err(err) => self.send_error(
+                        functionality,
+                        &format!("Invalid version restore payload: {err}"),
+                    ),
+                }
+            }
+            MSG_IGNORES_GET_REQUEST => {
+                match serde_json::from_str::<IgnoresGetRequest>(&message.contents) {
+                    Ok(req) => self.handle_ignores_get(functionality, req).await,
+                    Err(err) => {
+                        self.send_error(functionality, &format!("Invalid ignores payload: {err}"))
+                    }
+                }
+            }
+            MSG_IGNORES_SET_REQUEST => {
+                match serde_json::from_str::<IgnoresSetRequest>(&message.contents) {
+                    Ok(req) => self.handle_ignores_set(functionality, req).await,
+                    Err(err) => self.send_error(
+                        functionality,
+                        &format!("Invalid ignores update payload: {err}"),
+                    ),
+                }
+            }
+            MSG_CHANGES_QUERY_REQUEST => {
+                match serde_json::from_str::<ChangesQueryRequest>(&message.contents) {
+                    Ok(req) => self.handle_changes_query(functionality, req),
+                    Err(err) => self.send_error(
+                        functionality,
+                        &format!("Invalid changes query payload: {err}"),
+                    ),
+                }
+            }
+            MSG_SYSTEM_LOG_REQUEST => {
+                self.handle_system_log(functionality).await;
+            }
+            MSG_SYSTEM_ERRORS_CLEAR_REQUEST => {
+                self.handle_system_errors_clear(functionality).await;
+            }
+            MSG_BANDWIDTH_GET_REQUEST => {
+                self.handle_bandwidth_get(functionality).await;
+            }
+            MSG_BANDWIDTH_SET_REQUEST => {
+                match serde_json::from_str::<BandwidthSetRequest>(&message.contents) {
+                    Ok(req) => self.handle_bandwidth_set(functionality, req).await,
+                    Err(err) => {
+                        self.send_error(functionality, &format!("Invalid bandwidth payload: {err}"))
+                    }
+                }
+            }
             MSG_UPDATE_CHECK_REQUEST => {
                 self.handle_update_check(functionality).await;
             }
@@ -0,0 +1,88 @@
+use appload_client::BackendReplier;
+use serde_json::json;
+use tracing::error;
+
+use super::super::protocol::{BandwidthSetRequest, MSG_BANDWIDTH_RESULT, MSG_BANDWIDTH_SET_RESULT};
+use super::super::Backend;
+
+impl Backend {
+    /// Send the global and per-peer rate limits
+    pub async fn handle_bandwidth_get(&mut self, functionality: &BackendReplier<Self>) {
+        let Some(client) = &mut self.client else {
+            self.send_error(functionality, "Syncthing client not available");
+            return;
+        };
+
+        let payload = match client.bandwidth_limits().await {
+            Ok(limits) => json!({
+                "ok": true,
+                "global": limits.global,
+                "devices": limits.devices,
+            }),
+            Err(err) => json!({
+                "ok": false,
+                "message": format!("Failed to fetch bandwidth limits: {}", err)
+            }),
+        };
+        if let Err(err) = functionality.send_message(MSG_BANDWIDTH_RESULT, &payload.to_string()) {
+            error!(error = ?err, "Failed to send bandwidth limits");
+        }
+    }
+
+    /// Set the global or a peer's rate limits
+    pub async fn handle_bandwidth_set(
+        &mut self,
+        functionality: &BackendReplier<Self>,
+        req: BandwidthSetRequest,
+    ) {
+        let Some(client) = &mut self.client else {
+            self.send_error(functionality, "Syncthing client not available");
+            return;
+        };
+
+        let result = client
+            .set_bandwidth_limits(
+                req.device_id.as_deref(),
+                req.max_send_kbps,
+                req.max_recv_kbps,
+            )
+            .await;
+        let target = match &req.device_id {
+            Some(device_id) => format!("device {device_id}"),
+            None => "all devices".to_string(),
+        };
+
+        match result {
+            Ok(()) => {
+                let payload = json!({
+                    "ok": true,
+                    "device_id": req.device_id,
+                    "max_send_kbps": req.max_send_kbps,
+                    "max_recv_kbps": req.max_recv_kbps,
+                    "message": format!("Bandwidth limits updated for {}", target)
+                });
+                if let Err(err) =
+                    functionality.send_message(MSG_BANDWIDTH_SET_RESULT, &payload.to_string())
+                {
+                    error!(error = ?err, "Failed to send bandwidth result");
+                }
+                self.send_status(functionality, "bandwidth").await;
+                self.handle_bandwidth_get(functionality).await;
+            }
+            Err(err) => {
+                let payload = json!({
+                    "ok": false,
+                    "device_id": req.device_id,
+                    "max_send_kbps": req.max_send_kbps,
+                    "max_recv_kbps": req.max_recv_kbps,
+                    "message": format!("Failed to update bandwidth limits for {}: {}", target, err)
+                });
+                if let Err(send_err) =
+                    functionality.send_message(MSG_BANDWIDTH_SET_RESULT, &payload.to_string())
+                {
+                    error!(error = ?send_err, "Failed to send bandwidth result");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use appload_client::BackendReplier;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use tracing::error;
+
+use super::super::protocol::{ChangesQueryRequest, MSG_CHANGES_QUERY_RESULT};
+use super::super::Backend;
+
+const DEFAULT_CHANGES_PER_PAGE: usize = 50;
+
+impl Backend {
+    /// Query the change journal by folder, peer and time
+    pub fn handle_changes_query(
+        &mut self,
+        functionality: &BackendReplier<Self>,
+        req: ChangesQueryRequest,
+    ) {
+        let since = match req.since.as_deref().map(DateTime::parse_from_rfc3339) {
+            Some(Ok(since)) => Some(since.with_timezone(&Utc)),
+            Some(Err(err)) => {
+                self.send_error(
+                    functionality,
+                    &format!("Invalid changes query timestamp: {err}"),
+                );
+                return;
+            }
+            None => None,
+        };
+        let page = req.page.unwrap_or(1).max(1);
+        let per_page = req.per_page.unwrap_or(DEFAULT_CHANGES_PER_PAGE);
+
+        let (changes, total) = self.journal.query(
+            req.folder_id.as_deref(),
+            req.device_id.as_deref(),
+            since,
+            page,
+            per_page,
+        );
+        let payload = json!({
+            "ok": true,
+            "folder_id": req.folder_id,
+            "device_id": req.device_id,
+            "since": req.since,
+            "page": page,
+            "total": total,
+            "changes": changes,
+        });
+        if let Err(err) = functionality.send_message(MSG_CHANGES_QUERY_RESULT, &payload.to_string())
+        {
+            error!(error = ?err, "Failed to send changes query result");
+        }
+    }
+}
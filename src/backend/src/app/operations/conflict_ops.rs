@@ -0,0 +1,90 @@
+use appload_client::BackendReplier;
+use serde_json::json;
+use tracing::{error, warn};
+
+use super::super::protocol::{
+    ConflictResolveRequest, ConflictScanRequest, MSG_CONFLICT_RESOLVE_RESULT,
+    MSG_CONFLICT_SCAN_RESULT,
+};
+use super::super::Backend;
+
+impl Backend {
+    /// Scan folders on disk for sync-conflict copies
+    pub async fn handle_conflict_scan(
+        &mut self,
+        functionality: &BackendReplier<Self>,
+        req: ConflictScanRequest,
+    ) {
+        let Some(client) = &mut self.client else {
+            self.send_error(functionality, "Syncthing client not available");
+            return;
+        };
+
+        let payload = match client.scan_conflicts(req.folder_id.as_deref()).await {
+            Ok(folders) => json!({
+                "ok": true,
+                "folder_id": req.folder_id,
+                "folders": folders,
+            }),
+            Err(err) => json!({
+                "ok": false,
+                "folder_id": req.folder_id,
+                "folders": [],
+                "message": format!("Failed to scan for conflicts: {}", err)
+            }),
+        };
+        if let Err(err) = functionality.send_message(MSG_CONFLICT_SCAN_RESULT, &payload.to_string())
+        {
+            error!(error = ?err, "Failed to send conflict scan result");
+        }
+    }
+
+    /// Resolve a conflict copy, then rescan the folder so Syncthing and the UI pick up the change
+    pub async fn handle_conflict_resolve(
+        &mut self,
+        functionality: &BackendReplier<Self>,
+        req: ConflictResolveRequest,
+    ) {
+        let Some(client) = &mut self.client else {
+            self.send_error(functionality, "Syncthing client not available");
+            return;
+        };
+
+        let result = client
+            .resolve_conflict(&req.folder_id, &req.path, req.keep)
+            .await;
+        let succeeded = result.is_ok();
+        if succeeded {
+            if let Err(err) = client.rescan(Some(&req.folder_id)).await {
+                warn!(folder = %req.folder_id, error = ?err, "Failed to rescan after resolving conflict");
+            }
+        }
+
+        let payload = match result {
+            Ok(message) => json!({
+                "ok": true,
+                "folder_id": req.folder_id,
+                "path": req.path,
+                "message": message
+            }),
+            Err(err) => json!({
+                "ok": false,
+                "folder_id": req.folder_id,
+                "path": req.path,
+                "message": format!("Failed to resolve conflict {}: {}", req.path, err)
+            }),
+        };
+        if let Err(err) =
+            functionality.send_message(MSG_CONFLICT_RESOLVE_RESULT, &payload.to_string())
+        {
+            error!(error = ?err, "Failed to send conflict resolve result");
+        }
+
+        if succeeded {
+            let scan = ConflictScanRequest {
+                folder_id: Some(req.folder_id),
+            };
+            self.handle_conflict_scan(functionality, scan).await;
+        }
+    }
+}
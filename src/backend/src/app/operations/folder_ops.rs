@@ -0,0 +1,109 @@
+use appload_client::BackendReplier;
+use serde_json::json;
+use tracing::error;
+
+use crate::types::MonitorError;
+
+use super::super::protocol::{
+    FolderCreateRequest, FolderDeleteRequest, FolderUpdateRequest, MSG_FOLDER_MANAGE_RESULT,
+};
+use super::super::Backend;
+
+impl Backend {
+    /// Create a new shared folder via Syncthing API
+    pub async fn handle_folder_create(
+        &mut self,
+        functionality: &BackendReplier<Self>,
+        req: FolderCreateRequest,
+    ) {
+        let Some(client) = &mut self.client else {
+            self.send_error(functionality, "Syncthing client not available");
+            return;
+        };
+        let result = client
+            .create_folder(
+                &req.folder_id,
+                req.label.as_deref(),
+                req.path.as_deref(),
+                req.folder_type,
+                &req.device_ids,
+            )
+            .await
+            .map(|path| format!("Folder {} created at {}", req.folder_id, path));
+        self.finish_folder_operation(functionality, "create", &req.folder_id, result)
+            .await;
+    }
+
+    /// Update label, type or shared devices of an existing folder
+    pub async fn handle_folder_update(
+        &mut self,
+        functionality: &BackendReplier<Self>,
+        req: FolderUpdateRequest,
+    ) {
+        let Some(client) = &mut self.client else {
+            self.send_error(functionality, "Syncthing client not available");
+            return;
+        };
+        let result = client
+            .update_folder(
+                &req.folder_id,
+                req.label.as_deref(),
+                req.folder_type,
+                req.device_ids.as_deref(),
+            )
+            .await
+            .map(|()| format!("Folder {} updated", req.folder_id));
+        self.finish_folder_operation(functionality, "update", &req.folder_id, result)
+            .await;
+    }
+
+    /// Remove a folder from the Syncthing configuration
+    pub async fn handle_folder_delete(
+        &mut self,
+        functionality: &BackendReplier<Self>,
+        req: FolderDeleteRequest,
+    ) {
+        let Some(client) = &mut self.client else {
+            self.send_error(functionality, "Syncthing client not available");
+            return;
+        };
+        let result = client
+            .delete_folder(&req.folder_id)
+            .await
+            .map(|()| format!("Folder {} removed", req.folder_id));
+        self.finish_folder_operation(functionality, "delete", &req.folder_id, result)
+            .await;
+    }
+
+    /// Reports the outcome of a folder operation and refreshes status on success.
+    async fn finish_folder_operation(
+        &mut self,
+        functionality: &BackendReplier<Self>,
+        operation: &str,
+        folder_id: &str,
+        result: Result<String, MonitorError>,
+    ) {
+        let succeeded = result.is_ok();
+        let payload = match result {
+            Ok(message) => json!({
+                "ok": true,
+                "operation": operation,
+                "folder_id": folder_id,
+                "message": message
+            }),
+            Err(err) => json!({
+                "ok": false,
+                "operation": operation,
+                "folder_id": folder_id,
+                "message": format!("Failed to {} folder {}: {}", operation, folder_id, err)
+            }),
+        };
+        if let Err(err) = functionality.send_message(MSG_FOLDER_MANAGE_RESULT, &payload.to_string())
+        {
+            error!(error = ?err, "Failed to send folder operation result");
+        }
+        if succeeded {
+            self.send_status(functionality, "folder-manage").await;
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use appload_client::BackendReplier;
+use serde_json::json;
+use tracing::error;
+
+use super::super::protocol::{
+    IgnoresGetRequest, IgnoresSetRequest, MSG_IGNORES_RESULT, MSG_IGNORES_SET_RESULT,
+};
+use super::super::Backend;
+
+impl Backend {
+    /// Fetch the ignore patterns of a folder
+    pub async fn handle_ignores_get(
+        &mut self,
+        functionality: &BackendReplier<Self>,
+        req: IgnoresGetRequest,
+    ) {
+        let Some(client) = &mut self.client else {
+            self.send_error(functionality, "Syncthing client not available");
+            return;
+        };
+
+        let payload = match client.get_ignores(&req.folder_id).await {
+            Ok(patterns) => json!({
+                "ok": true,
+                "folder_id": req.folder_id,
+                "patterns": patterns,
+            }),
+            Err(err) => json!({
+                "ok": false,
+                "folder_id": req.folder_id,
+                "patterns": [],
+                "message": format!("Failed to load ignore patterns: {}", err)
+            }),
+        };
+        if let Err(err) = functionality.send_message(MSG_IGNORES_RESULT, &payload.to_string()) {
+            error!(error = ?err, "Failed to send ignore patterns");
+        }
+    }
+
+    /// Validate and preview new ignore patterns, saving them unless this is a dry run
+    pub async fn handle_ignores_set(
+        &mut self,
+        functionality: &BackendReplier<Self>,
+        req: IgnoresSetRequest,
+    ) {
+        let Some(client) = &mut self.client else {
+            self.send_error(functionality, "Syncthing client not available");
+            return;
+        };
+
+        let result = match client.preview_ignores(&req.folder_id, &req.patterns).await {
+            Ok(preview) if req.dry_run => Ok((preview, false)),
+            Ok(preview) => client
+                .set_ignores(&req.folder_id, &req.patterns)
+                .await
+                .map(|()| (preview, true)),
+            Err(err) => Err(err),
+        };
+
+        let saved = matches!(result, Ok((_, true)));
+        let payload = match result {
+            Ok((preview, saved)) => json!({
+                "ok": true,
+                "folder_id": req.folder_id,
+                "saved": saved,
+                "patterns": req.patterns,
+                "preview": preview,
+                "message": if saved {
+                    format!("Ignore patterns of {} saved", req.folder_id)
+                } else {
+                    format!(
+                        "{} files would be ignored, {} un-ignored",
+                        preview.newly_ignored.len(),
+                        preview.newly_unignored.len()
+                    )
+                }
+            }),
+            Err(err) => json!({
+                "ok": false,
+                "folder_id": req.folder_id,
+                "saved": false,
+                "patterns": req.patterns,
+                "message": format!("Failed to update ignore patterns: {}", err)
+            }),
+        };
+        if let Err(err) = functionality.send_message(MSG_IGNORES_SET_RESULT, &payload.to_string()) {
+            error!(error = ?err, "Failed to send ignore update result");
+        }
+        if saved {
+            self.send_status(functionality, "ignores").await;
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use appload_client::BackendReplier;
+use serde_json::json;
+use tracing::error;
+
+use super::super::protocol::{MSG_SYSTEM_ERRORS_CLEAR_RESULT, MSG_SYSTEM_LOG_RESULT};
+use super::super::Backend;
+
+impl Backend {
+    /// Fetch recent lines of the Syncthing log
+    pub async fn handle_system_log(&mut self, functionality: &BackendReplier<Self>) {
+        let Some(client) = &mut self.client else {
+            self.send_error(functionality, "Syncthing client not available");
+            return;
+        };
+
+        let payload = match client.system_log().await {
+            Ok(entries) => json!({
+                "ok": true,
+                "entries": entries,
+            }),
+            Err(err) => json!({
+                "ok": false,
+                "entries": [],
+                "message": format!("Failed to fetch Syncthing log: {}", err)
+            }),
+        };
+        if let Err(err) = functionality.send_message(MSG_SYSTEM_LOG_RESULT, &payload.to_string()) {
+            error!(error = ?err, "Failed to send system log");
+        }
+    }
+
+    /// Clear the warnings Syncthing reports
+    pub async fn handle_system_errors_clear(&mut self, functionality: &BackendReplier<Self>) {
+        let Some(client) = &mut self.client else {
+            self.send_error(functionality, "Syncthing client not available");
+            return;
+        };
+
+        match client.clear_system_errors().await {
+            Ok(()) => {
+                let payload = json!({
+                    "ok": true,
+                    "message": "Syncthing warnings cleared"
+                });
+                if let Err(err) =
+                    functionality.send_message(MSG_SYSTEM_ERRORS_CLEAR_RESULT, &payload.to_string())
+                {
+                    error!(error = ?err, "Failed to send clear warnings result");
+                }
+                self.send_status(functionality, "warnings-cleared").await;
+            }
+            Err(err) => {
+                let payload = json!({
+                    "ok": false,
+                    "message": format!("Failed to clear warnings: {}", err)
+                });
+                if let Err(send_err) =
+                    functionality.send_message(MSG_SYSTEM_ERRORS_CLEAR_RESULT, &payload.to_string())
+                {
+                    error!(error = ?send_err, "Failed to send clear warnings result");
+                }
+            }
+        }
+    }
+}
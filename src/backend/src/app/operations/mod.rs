@@ -1,4 +1,11 @@
+mod bandwidth_ops;
+mod changes_ops;
+mod conflict_ops;
+mod folder_ops;
+mod ignore_ops;
 mod installer_ops;
+mod log_ops;
 mod syncthing_ops;
 mod update_ops;
+mod version_ops;
 
@@ -4,9 +4,14 @@ use tracing::{error, info, warn};
 
 use crate::systemd::{control_service, ServiceAction};
 use crate::syncthing_client::SyncthingClient;
+use crate::utils::qr::render_qr;
 
 use super::super::protocol::{
-    ControlRequest, GuiAddressToggleRequest, MSG_CONTROL_RESULT, MSG_GUI_ADDRESS_RESULT,
+    ControlRequest, DeviceQrRequest, FolderPauseRequest, GuiAddressToggleRequest,
+    GuiAuthSetRequest, PeerPauseRequest, PendingAction, PendingDeviceRequest, PendingFolderAction,
+    PendingFolderRequest, RescanRequest, MSG_CONTROL_RESULT, MSG_DEVICE_QR_RESULT,
+    MSG_FOLDER_PAUSE_RESULT, MSG_GUI_ADDRESS_RESULT, MSG_GUI_AUTH_SET_RESULT,
+    MSG_PEER_PAUSE_RESULT, MSG_PENDING_DEVICE_RESULT, MSG_PENDING_FOLDER_RESULT, MSG_RESCAN_RESULT,
 };
 use super::super::Backend;
 
@@ -128,5 +133,412 @@ impl Backend {
             self.send_error(functionality, "Syncthing client not available");
         }
     }
-}
 
+    /// Set the GUI login via Syncthing API, optionally switching the GUI to HTTPS.
+    /// The password is never echoed back to the frontend.
+    pub async fn handle_gui_auth_set(
+        &mut self,
+        functionality: &BackendReplier<Self>,
+        req: GuiAuthSetRequest,
+    ) {
+        if let Some(client) = &mut self.client {
+            match client
+                .set_gui_auth(&req.user, &req.password, req.enable_tls)
+                .await
+            {
+                Ok(()) => {
+                    let message = if req.enable_tls {
+                        "GUI login set and HTTPS enabled"
+                    } else {
+                        "GUI login set"
+                    };
+                    let payload = json!({
+                        "ok": true,
+                        "user": req.user.trim(),
+                        "tls": req.enable_tls,
+                        "message": message
+                    });
+                    if let Err(err) =
+                        functionality.send_message(MSG_GUI_AUTH_SET_RESULT, &payload.to_string())
+                    {
+                        error!(error = ?err, "Failed to send GUI auth result");
+                    }
+                    self.send_status(functionality, "gui-auth").await;
+                }
+                Err(err) => {
+                    let payload = json!({
+                        "ok": false,
+                        "user": req.user.trim(),
+                        "tls": req.enable_tls,
+                        "message": format!("Failed to set GUI login: {}", err)
+                    });
+                    if let Err(send_err) =
+                        functionality.send_message(MSG_GUI_AUTH_SET_RESULT, &payload.to_string())
+                    {
+                        error!(error = ?send_err, "Failed to send GUI auth error");
+                    }
+                }
+            }
+        } else {
+            self.send_error(functionality, "Syncthing client not available");
+        }
+    }
+
+    /// Pause or resume a single folder via Syncthing API
+    pub async fn handle_folder_pause(
+        &mut self,
+        functionality: &BackendReplier<Self>,
+        req: FolderPauseRequest,
+    ) {
+        let verb = if req.paused { "paused" } else { "resumed" };
+        if let Some(client) = &mut self.client {
+            match client.set_folder_paused(&req.folder_id, req.paused).await {
+                Ok(()) => {
+                    let payload = json!({
+                        "ok": true,
+                        "folder_id": req.folder_id,
+                        "paused": req.paused,
+                        "message": format!("Folder {} {}", req.folder_id, verb)
+                    });
+                    if let Err(err) = functionality
+                        .send_message(MSG_FOLDER_PAUSE_RESULT, &payload.to_string())
+                    {
+                        error!(error = ?err, "Failed to send folder pause result");
+                    }
+                    self.send_status(functionality, "folder-pause").await;
+                }
+                Err(err) => {
+                    let payload = json!({
+                        "ok": false,
+                        "folder_id": req.folder_id,
+                        "paused": req.paused,
+                        "message": format!("Failed to update folder {}: {}", req.folder_id, err)
+                    });
+                    if let Err(send_err) = functionality
+                        .send_message(MSG_FOLDER_PAUSE_RESULT, &payload.to_string())
+                    {
+                        error!(error = ?send_err, "Failed to send folder pause error");
+                    }
+                }
+            }
+        } else {
+            self.send_error(functionality, "Syncthing client not available");
+        }
+    }
+
+    /// Pause or resume a single peer, or all peers when no device is given
+    pub async fn handle_peer_pause(
+        &mut self,
+        functionality: &BackendReplier<Self>,
+        req: PeerPauseRequest,
+    ) {
+        let verb = if req.paused { "paused" } else { "resumed" };
+        let target = match &req.device_id {
+            Some(device_id) => format!("Device {}", device_id),
+            None => "All devices".to_string(),
+        };
+        if let Some(client) = &mut self.client {
+            let result = match &req.device_id {
+                Some(device_id) => client.set_device_paused(device_id, req.paused).await,
+                None => client.set_all_devices_paused(req.paused).await,
+            };
+            match result {
+                Ok(()) => {
+                    let payload = json!({
+                        "ok": true,
+                        "device_id": req.device_id,
+                        "paused": req.paused,
+                        "message": format!("{} {}", target, verb)
+                    });
+                    if let Err(err) = functionality
+                        .send_message(MSG_PEER_PAUSE_RESULT, &payload.to_string())
+                    {
+                        error!(error = ?err, "Failed to send peer pause result");
+                    }
+                    self.send_status(functionality, "peer-pause").await;
+                }
+                Err(err) => {
+                    let payload = json!({
+                        "ok": false,
+                        "device_id": req.device_id,
+                        "paused": req.paused,
+                        "message": format!("Failed to update {}: {}", target.to_lowercase(), err)
+                    });
+                    if let Err(send_err) = functionality
+                        .send_message(MSG_PEER_PAUSE_RESULT, &payload.to_string())
+                    {
+                        error!(error = ?send_err, "Failed to send peer pause error");
+                    }
+                }
+            }
+        } else {
+            self.send_error(functionality, "Syncthing client not available");
+        }
+    }
+
+    /// Trigger an on-demand rescan of one folder or all folders.
+    /// Completion is reported later by the event stream via `complete_rescans`.
+    pub async fn handle_rescan(
+        &mut self,
+        functionality: &BackendReplier<Self>,
+        req: RescanRequest,
+    ) {
+        let Some(client) = &mut self.client else {
+            self.send_error(functionality, "Syncthing client not available");
+            return;
+        };
+
+        let targets = match &req.folder_id {
+            Some(folder_id) => Ok(vec![folder_id.clone()]),
+            None => client.active_folder_ids().await,
+        };
+        let result = match targets {
+            Ok(targets) => client
+                .rescan(req.folder_id.as_deref())
+                .await
+                .map(|()| targets),
+            Err(err) => Err(err),
+        };
+
+        match result {
+            Ok(targets) => {
+                self.pending_rescans.extend(targets);
+                let payload = json!({
+                    "ok": true,
+                    "folder_id": req.folder_id,
+                    "finished": self.pending_rescans.is_empty(),
+                    "remaining": self.pending_rescans.len(),
+                    "message": match &req.folder_id {
+                        Some(folder_id) => format!("Rescan of {} requested", folder_id),
+                        None => "Rescan of all folders requested".to_string(),
+                    }
+                });
+                if let Err(err) =
+                    functionality.send_message(MSG_RESCAN_RESULT, &payload.to_string())
+                {
+                    error!(error = ?err, "Failed to send rescan result");
+                }
+                self.send_status(functionality, "rescan").await;
+            }
+            Err(err) => {
+                let payload = json!({
+                    "ok": false,
+                    "folder_id": req.folder_id,
+                    "finished": true,
+                    "remaining": self.pending_rescans.len(),
+                    "message": format!("Failed to trigger rescan: {}", err)
+                });
+                if let Err(send_err) =
+                    functionality.send_message(MSG_RESCAN_RESULT, &payload.to_string())
+                {
+                    error!(error = ?send_err, "Failed to send rescan error");
+                }
+            }
+        }
+    }
+
+    /// Reports finished scans for folders that had a rescan requested from the UI.
+    pub fn complete_rescans(&mut self, functionality: &BackendReplier<Self>, folders: &[String]) {
+        for folder_id in folders {
+            if !self.pending_rescans.remove(folder_id) {
+                continue;
+            }
+            let payload = json!({
+                "ok": true,
+                "folder_id": folder_id,
+                "finished": true,
+                "remaining": self.pending_rescans.len(),
+                "message": format!("Rescan of {} finished", folder_id)
+            });
+            if let Err(err) = functionality.send_message(MSG_RESCAN_RESULT, &payload.to_string()) {
+                error!(error = ?err, "Failed to send rescan completion");
+            }
+        }
+    }
+
+    /// Ends tracking of all requested rescans when their completion can no
+    /// longer be observed, because events were dropped or the event stream
+    /// reconnected.
+    pub fn abandon_rescans(&mut self, functionality: &BackendReplier<Self>, reason: &str) {
+        let mut folders: Vec<String> = self.pending_rescans.drain().collect();
+        folders.sort();
+        let total = folders.len();
+        for (index, folder_id) in folders.iter().enumerate() {
+            let payload = json!({
+                "ok": true,
+                "folder_id": folder_id,
+                "finished": true,
+                "remaining": total - index - 1,
+                "message": format!("Rescan of {} requested, completion unknown ({})", folder_id, reason)
+            });
+            if let Err(err) = functionality.send_message(MSG_RESCAN_RESULT, &payload.to_string()) {
+                error!(error = ?err, "Failed to send rescan completion");
+            }
+        }
+    }
+
+    /// Accept or dismiss a device that is waiting to connect
+    pub async fn handle_pending_device(
+        &mut self,
+        functionality: &BackendReplier<Self>,
+        req: PendingDeviceRequest,
+    ) {
+        if let Some(client) = &mut self.client {
+            let result = match req.action {
+                PendingAction::Accept => {
+                    let name = req
+                        .name
+                        .as_deref()
+                        .map(str::trim)
+                        .filter(|name| !name.is_empty())
+                        .unwrap_or(&req.device_id);
+                    client.accept_pending_device(&req.device_id, name).await
+                }
+                PendingAction::Dismiss => client.dismiss_pending_device(&req.device_id).await,
+            };
+            match result {
+                Ok(()) => {
+                    let verb = match req.action {
+                        PendingAction::Accept => "accepted",
+                        PendingAction::Dismiss => "dismissed",
+                    };
+                    let payload = json!({
+                        "ok": true,
+                        "device_id": req.device_id,
+                        "action": req.action.as_str(),
+                        "message": format!("Device {} {}", req.device_id, verb)
+                    });
+                    if let Err(err) = functionality
+                        .send_message(MSG_PENDING_DEVICE_RESULT, &payload.to_string())
+                    {
+                        error!(error = ?err, "Failed to send pending device result");
+                    }
+                    self.send_status(functionality, "pending-device").await;
+                }
+                Err(err) => {
+                    let payload = json!({
+                        "ok": false,
+                        "device_id": req.device_id,
+                        "action": req.action.as_str(),
+                        "message": format!(
+                            "Failed to {} device {}: {}",
+                            req.action.as_str(),
+                            req.device_id,
+                            err
+                        )
+                    });
+                    if let Err(send_err) = functionality
+                        .send_message(MSG_PENDING_DEVICE_RESULT, &payload.to_string())
+                    {
+                        error!(error = ?send_err, "Failed to send pending device error");
+                    }
+                }
+            }
+        } else {
+            self.send_error(functionality, "Syncthing client not available");
+        }
+    }
+
+    /// Accept a folder share offered by a peer, or ignore it
+    pub async fn handle_pending_folder(
+        &mut self,
+        functionality: &BackendReplier<Self>,
+        req: PendingFolderRequest,
+    ) {
+        if let Some(client) = &mut self.client {
+            let result = match req.action {
+                PendingFolderAction::Accept => client
+                    .accept_pending_folder(&req.folder_id, req.path.as_deref())
+                    .await
+                    .map(|path| format!("Folder {} accepted into {}", req.folder_id, path)),
+                PendingFolderAction::Ignore => client
+                    .ignore_pending_folder(&req.folder_id)
+                    .await
+                    .map(|()| format!("Folder {} ignored", req.folder_id)),
+            };
+            match result {
+                Ok(message) => {
+                    let payload = json!({
+                        "ok": true,
+                        "folder_id": req.folder_id,
+                        "action": req.action.as_str(),
+                        "message": message
+                    });
+                    if let Err(err) = functionality
+                        .send_message(MSG_PENDING_FOLDER_RESULT, &payload.to_string())
+                    {
+                        error!(error = ?err, "Failed to send pending folder result");
+                    }
+                    self.send_status(functionality, "pending-folder").await;
+                }
+                Err(err) => {
+                    let payload = json!({
+                        "ok": false,
+                        "folder_id": req.folder_id,
+                        "action": req.action.as_str(),
+                        "message": format!(
+                            "Failed to {} folder {}: {}",
+                            req.action.as_str(),
+                            req.folder_id,
+                            err
+                        )
+                    });
+                    if let Err(send_err) = functionality
+                        .send_message(MSG_PENDING_FOLDER_RESULT, &payload.to_string())
+                    {
+                        error!(error = ?send_err, "Failed to send pending folder error");
+                    }
+                }
+            }
+        } else {
+            self.send_error(functionality, "Syncthing client not available");
+        }
+    }
+
+    /// Render the local device ID (and optionally the GUI URL) as QR codes for pairing
+    pub async fn handle_device_qr(
+        &mut self,
+        functionality: &BackendReplier<Self>,
+        req: DeviceQrRequest,
+    ) {
+        let Some(client) = &mut self.client else {
+            self.send_error(functionality, "Syncthing client not available");
+            return;
+        };
+
+        let device_id = match client.device_id().await {
+            Ok(device_id) => device_id,
+            Err(err) => {
+                self.send_error(functionality, &format!("Failed to read device ID: {err}"));
+                return;
+            }
+        };
+        let gui_url = if req.include_gui_url {
+            client.get_gui_url().await.ok().flatten()
+        } else {
+            None
+        };
+
+        let device_qr = match render_qr(&device_id) {
+            Ok(qr) => qr,
+            Err(err) => {
+                self.send_error(
+                    functionality,
+                    &format!("Failed to render device QR code: {err}"),
+                );
+                return;
+            }
+        };
+        let gui_qr = gui_url.as_deref().and_then(|url| render_qr(url).ok());
+
+        let payload = json!({
+            "device_id": device_id,
+            "device_qr": device_qr,
+            "gui_url": gui_url,
+            "gui_qr": gui_qr,
+        });
+        if let Err(err) = functionality.send_message(MSG_DEVICE_QR_RESULT, &payload.to_string()) {
+            error!(error = ?err, "Failed to send device QR code");
+        }
+    }
+}
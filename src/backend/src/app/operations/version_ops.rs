@@ -0,0 +1,109 @@
+use appload_client::BackendReplier;
+use serde_json::json;
+use tracing::{error, warn};
+
+use super::super::protocol::{
+    VersionRestoreRequest, VersionsListRequest, MSG_VERSIONS_LIST_RESULT,
+    MSG_VERSION_RESTORE_RESULT,
+};
+use super::super::Backend;
+
+impl Backend {
+    /// List files with stored versions in a folder
+    pub async fn handle_versions_list(
+        &mut self,
+        functionality: &BackendReplier<Self>,
+        req: VersionsListRequest,
+    ) {
+        let Some(client) = &mut self.client else {
+            self.send_error(functionality, "Syncthing client not available");
+            return;
+        };
+
+        let payload = match client
+            .list_versions(&req.folder_id, req.prefix.as_deref())
+            .await
+        {
+            Ok((files, truncated)) => json!({
+                "ok": true,
+                "folder_id": req.folder_id,
+                "prefix": req.prefix,
+                "files": files,
+                "truncated": truncated,
+            }),
+            Err(err) => json!({
+                "ok": false,
+                "folder_id": req.folder_id,
+                "prefix": req.prefix,
+                "files": [],
+                "truncated": false,
+                "message": format!("Failed to list versions: {}", err)
+            }),
+        };
+        if let Err(err) = functionality.send_message(MSG_VERSIONS_LIST_RESULT, &payload.to_string())
+        {
+            error!(error = ?err, "Failed to send versions list");
+        }
+    }
+
+    /// Restore a stored file version and rescan the folder so the restored copy propagates
+    pub async fn handle_version_restore(
+        &mut self,
+        functionality: &BackendReplier<Self>,
+        req: VersionRestoreRequest,
+    ) {
+        let Some(client) = &mut self.client else {
+            self.send_error(functionality, "Syncthing client not available");
+            return;
+        };
+
+        let result = client
+            .restore_version(&req.folder_id, &req.path, &req.version_time)
+            .await;
+        let rescanning = match &result {
+            Ok(()) => match client.rescan(Some(&req.folder_id)).await {
+                Ok(()) => true,
+                Err(err) => {
+                    warn!(folder = %req.folder_id, error = ?err, "Failed to rescan after restoring version");
+                    false
+                }
+            },
+            Err(_) => false,
+        };
+
+        match result {
+            Ok(()) => {
+                if rescanning {
+                    self.pending_rescans.insert(req.folder_id.clone());
+                }
+                let payload = json!({
+                    "ok": true,
+                    "folder_id": req.folder_id,
+                    "path": req.path,
+                    "version_time": req.version_time,
+                    "message": format!("Restored {} from {}", req.path, req.version_time)
+                });
+                if let Err(err) =
+                    functionality.send_message(MSG_VERSION_RESTORE_RESULT, &payload.to_string())
+                {
+                    error!(error = ?err, "Failed to send version restore result");
+                }
+                self.send_status(functionality, "version-restore").await;
+            }
+            Err(err) => {
+                let payload = json!({
+                    "ok": false,
+                    "folder_id": req.folder_id,
+                    "path": req.path,
+                    "version_time": req.version_time,
+                    "message": format!("Failed to restore {}: {}", req.path, err)
+                });
+                if let Err(send_err) =
+                    functionality.send_message(MSG_VERSION_RESTORE_RESULT, &payload.to_string())
+                {
+                    error!(error = ?send_err, "Failed to send version restore result");
+                }
+            }
+        }
+    }
+}
@@ -8,6 +8,27 @@ pub const MSG_GUI_ADDRESS_TOGGLE: u32 = 3;
 pub const MSG_UPDATE_CHECK_REQUEST: u32 = 4;
 pub const MSG_UPDATE_DOWNLOAD_REQUEST: u32 = 5;
 pub const MSG_UPDATE_RESTART_REQUEST: u32 = 6;
+pub const MSG_FOLDER_PAUSE_REQUEST: u32 = 7;
+pub const MSG_PEER_PAUSE_REQUEST: u32 = 8;
+pub const MSG_RESCAN_REQUEST: u32 = 9;
+pub const MSG_PENDING_DEVICE_REQUEST: u32 = 10;
+pub const MSG_PENDING_FOLDER_REQUEST: u32 = 11;
+pub const MSG_FOLDER_CREATE_REQUEST: u32 = 12;
+pub const MSG_FOLDER_UPDATE_REQUEST: u32 = 13;
+pub const MSG_FOLDER_DELETE_REQUEST: u32 = 14;
+pub const MSG_DEVICE_QR_REQUEST: u32 = 15;
+pub const MSG_CONFLICT_SCAN_REQUEST: u32 = 16;
+pub const MSG_CONFLICT_RESOLVE_REQUEST: u32 = 17;
+pub const MSG_VERSIONS_LIST_REQUEST: u32 = 18;
+pub const MSG_VERSION_RESTORE_REQUEST: u32 = 19;
+pub const MSG_IGNORES_GET_REQUEST: u32 = 20;
+pub const MSG_IGNORES_SET_REQUEST: u32 = 21;
+pub const MSG_CHANGES_QUERY_REQUEST: u32 = 22;
+pub const MSG_SYSTEM_LOG_REQUEST: u32 = 23;
+pub const MSG_SYSTEM_ERRORS_CLEAR_REQUEST: u32 = 24;
+pub const MSG_BANDWIDTH_GET_REQUEST: u32 = 25;
+pub const MSG_BANDWIDTH_SET_REQUEST: u32 = 26;
+pub const MSG_GUI_AUTH_SET_REQUEST: u32 = 27;
 
 // Response messages (to frontend)
 pub const MSG_STATUS_UPDATE: u32 = 100;
@@ -16,12 +37,33 @@ pub const MSG_INSTALL_STATUS: u32 = 102;
 pub const MSG_GUI_ADDRESS_RESULT: u32 = 103;
 pub const MSG_UPDATE_CHECK_RESULT: u32 = 104;
 pub const MSG_UPDATE_DOWNLOAD_STATUS: u32 = 105;
+pub const MSG_FOLDER_PAUSE_RESULT: u32 = 106;
+pub const MSG_PEER_PAUSE_RESULT: u32 = 107;
+pub const MSG_RESCAN_RESULT: u32 = 108;
+pub const MSG_PENDING_DEVICE_RESULT: u32 = 109;
+pub const MSG_PENDING_FOLDER_RESULT: u32 = 110;
+pub const MSG_FOLDER_MANAGE_RESULT: u32 = 111;
+pub const MSG_DEVICE_QR_RESULT: u32 = 112;
+pub const MSG_CONFLICT_SCAN_RESULT: u32 = 113;
+pub const MSG_CONFLICT_RESOLVE_RESULT: u32 = 114;
+pub const MSG_VERSIONS_LIST_RESULT: u32 = 115;
+pub const MSG_VERSION_RESTORE_RESULT: u32 = 116;
+pub const MSG_IGNORES_RESULT: u32 = 117;
+pub const MSG_IGNORES_SET_RESULT: u32 = 118;
+pub const MSG_CHANGES_QUERY_RESULT: u32 = 119;
+pub const MSG_SYSTEM_LOG_RESULT: u32 = 120;
+pub const MSG_SYSTEM_ERRORS_CLEAR_RESULT: u32 = 121;
+pub const MSG_BANDWIDTH_RESULT: u32 = 122;
+pub const MSG_BANDWIDTH_SET_RESULT: u32 = 123;
+pub const MSG_GUI_AUTH_SET_RESULT: u32 = 124;
 pub const MSG_ERROR: u32 = 500;
 
 // Timing constants
 pub const UPDATE_RESTART_DELAY_SECS: u64 = 10;
 pub const EVENT_STREAM_TIMEOUT_SECS: u64 = 30;
 pub const EVENT_HEARTBEAT_SECS: u64 = 5;
-pub const EVENT_RECONNECT_DELAY_SECS: u64 = 5;
+pub const EVENT_RECONNECT_DELAY_SECS: u64 = 1;
+pub const EVENT_RECONNECT_MAX_DELAY_SECS: u64 = 60;
 pub const SYSTEMD_MONITOR_INTERVAL_SECS: u64 = 5;
+pub const SCHEDULER_INTERVAL_SECS: u64 = 30;
 
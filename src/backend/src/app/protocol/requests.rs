@@ -1,5 +1,6 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
+use crate::syncthing_client::{ConflictResolution, FolderType};
 use crate::systemd::ServiceAction;
 
 #[derive(Debug, Deserialize)]
@@ -12,3 +13,199 @@ pub struct GuiAddressToggleRequest {
     pub address: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct FolderPauseRequest {
+    pub folder_id: String,
+    pub paused: bool,
+}
+
+/// Pauses or resumes a single peer, or every peer when `device_id` is omitted.
+#[derive(Debug, Deserialize)]
+pub struct PeerPauseRequest {
+    #[serde(default)]
+    pub device_id: Option<String>,
+    pub paused: bool,
+}
+
+/// Rescans a single folder, or every folder when `folder_id` is omitted.
+#[derive(Debug, Deserialize)]
+pub struct RescanRequest {
+    #[serde(default)]
+    pub folder_id: Option<String>,
+}
+
+/// What to do with a pending device or folder offer.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PendingAction {
+    Accept,
+    Dismiss,
+}
+
+impl PendingAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PendingAction::Accept => "accept",
+            PendingAction::Dismiss => "dismiss",
+        }
+    }
+}
+
+/// Accepts (optionally under a chosen name) or dismisses a pending device.
+#[derive(Debug, Deserialize)]
+pub struct PendingDeviceRequest {
+    pub device_id: String,
+    pub action: PendingAction,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// What to do with a folder share offered by a remote device.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PendingFolderAction {
+    Accept,
+    Ignore,
+}
+
+impl PendingFolderAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PendingFolderAction::Accept => "accept",
+            PendingFolderAction::Ignore => "ignore",
+        }
+    }
+}
+
+/// Accepts a pending folder share into `path` (or the default path), or ignores it.
+#[derive(Debug, Deserialize)]
+pub struct PendingFolderRequest {
+    pub folder_id: String,
+    pub action: PendingFolderAction,
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+/// Creates a new folder shared with the given devices.
+#[derive(Debug, Deserialize)]
+pub struct FolderCreateRequest {
+    pub folder_id: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub folder_type: Option<FolderType>,
+    #[serde(default)]
+    pub device_ids: Vec<String>,
+}
+
+/// Updates an existing folder; omitted fields are left unchanged.
+#[derive(Debug, Deserialize)]
+pub struct FolderUpdateRequest {
+    pub folder_id: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub folder_type: Option<FolderType>,
+    #[serde(default)]
+    pub device_ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FolderDeleteRequest {
+    pub folder_id: String,
+}
+
+/// Requests the local device ID rendered as a QR code, optionally with the GUI URL.
+#[derive(Debug, Deserialize, Default)]
+pub struct DeviceQrRequest {
+    #[serde(default)]
+    pub include_gui_url: bool,
+}
+
+/// Scans a single folder for sync-conflict copies, or every folder when `folder_id` is omitted.
+#[derive(Debug, Deserialize, Default)]
+pub struct ConflictScanRequest {
+    #[serde(default)]
+    pub folder_id: Option<String>,
+}
+
+/// Resolves a conflict copy by keeping the local file, the conflict copy, or both.
+/// `path` is the conflict copy relative to the folder root.
+#[derive(Debug, Deserialize)]
+pub struct ConflictResolveRequest {
+    pub folder_id: String,
+    pub path: String,
+    pub keep: ConflictResolution,
+}
+
+/// Lists stored file versions of a folder, optionally only paths under `prefix`.
+#[derive(Debug, Deserialize)]
+pub struct VersionsListRequest {
+    pub folder_id: String,
+    #[serde(default)]
+    pub prefix: Option<String>,
+}
+
+/// Restores the version of `path` stored at `version_time`.
+#[derive(Debug, Deserialize)]
+pub struct VersionRestoreRequest {
+    pub folder_id: String,
+    pub path: String,
+    pub version_time: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IgnoresGetRequest {
+    pub folder_id: String,
+}
+
+/// Replaces the ignore patterns of a folder. With `dry_run` the patterns are
+/// only validated and previewed, not saved.
+#[derive(Debug, Deserialize)]
+pub struct IgnoresSetRequest {
+    pub folder_id: String,
+    pub patterns: Vec<String>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Pages through the change journal, newest first. `since` is an RFC 3339
+/// timestamp; `device_id` limits the result to changes made by that peer.
+/// `page` starts at 1.
+#[derive(Debug, Deserialize)]
+pub struct ChangesQueryRequest {
+    #[serde(default)]
+    pub folder_id: Option<String>,
+    #[serde(default)]
+    pub device_id: Option<String>,
+    #[serde(default)]
+    pub since: Option<String>,
+    #[serde(default)]
+    pub page: Option<usize>,
+    #[serde(default)]
+    pub per_page: Option<usize>,
+}
+
+/// Sets rate limits in kbit/s for `device_id`, or the global limits when it is
+/// omitted. Omitted limits are left unchanged; 0 removes a limit.
+#[derive(Debug, Deserialize)]
+pub struct BandwidthSetRequest {
+    #[serde(default)]
+    pub device_id: Option<String>,
+    #[serde(default)]
+    pub max_send_kbps: Option<u64>,
+    #[serde(default)]
+    pub max_recv_kbps: Option<u64>,
+}
+
+/// Sets the GUI login. With `enable_tls` the GUI is also switched to HTTPS;
+/// without it the TLS setting is left unchanged.
+#[derive(Debug, Deserialize)]
+pub struct GuiAuthSetRequest {
+    pub user: String,
+    pub password: String,
+    #[serde(default)]
+    pub enable_tls: bool,
+}
@@ -3,6 +3,7 @@ use tokio::task::JoinHandle;
 
 use super::event_stream;
 use super::protocol::SYSTEMD_MONITOR_INTERVAL_SECS;
+use super::scheduler;
 use super::Backend;
 
 impl Backend {
@@ -33,6 +34,14 @@ impl Backend {
                 .await;
             }));
         }
+
+        if !task_is_running(&self.scheduler_task) {
+            let config = self.config.clone();
+            let replier = functionality.clone();
+            self.scheduler_task = Some(tokio::spawn(async move {
+                scheduler::drive_schedule(replier, config).await;
+            }));
+        }
     }
 }
 
@@ -0,0 +1,292 @@
+use std::path::PathBuf;
+
+use appload_client::BackendReplier;
+use chrono::Local;
+use tokio::time::{interval, Duration};
+use tracing::{debug, info, warn};
+
+use crate::config::{Config, SuspendAction};
+use crate::network::detect_network;
+use crate::power::read_power_supply;
+use crate::scheduler::{PolicyStatus, SchedulerState};
+use crate::systemd::{control_service, ServiceAction};
+
+use super::protocol::SCHEDULER_INTERVAL_SECS;
+use super::Backend;
+
+/// Enforces the sync policies from `config.json`: the schedule, the battery
+/// policy and the network policy.
+///
+/// While any policy disallows syncing it pauses all active folders or stops
+/// the service, using the strongest action among them, and undoes exactly
+/// that once every policy allows syncing again. What it suspended is
+/// persisted, so a restarted backend picks up where it left off and also
+/// resumes sync if a policy was disabled in the meantime.
+pub async fn drive_schedule(functionality: BackendReplier<Backend>, config: Config) {
+    let schedule = &config.sync_schedule;
+    for problem in schedule.invalid_windows() {
+        warn!(problem = %problem, "Ignoring invalid sync window");
+    }
+
+    let state_path = Config::scheduler_state_path()
+        .map_err(|err| warn!(error = ?err, "Scheduler state will not be persisted"))
+        .ok();
+    let mut state = match &state_path {
+        Some(path) => SchedulerState::load(path).await,
+        None => SchedulerState::default(),
+    };
+    let power_supply_dir = PathBuf::from(&config.battery_policy.power_supply_dir);
+    let network_policy = &config.network_policy;
+    let tracks_usage = network_policy.enabled && !network_policy.metered_networks.is_empty();
+
+    let mut ticker = interval(Duration::from_secs(SCHEDULER_INTERVAL_SECS));
+    loop {
+        ticker.tick().await;
+        let now = Local::now();
+
+        let power = match read_power_supply(&power_supply_dir).await {
+            Ok(power) => power,
+            Err(err) => {
+                debug!(error = ?err, "Failed to read power supply");
+                None
+            }
+        };
+        let battery_low = config
+            .battery_policy
+            .battery_low(power.as_ref(), state.battery_low);
+
+        let network = detect_network(network_policy).await;
+        let mut dirty = false;
+        if tracks_usage {
+            if let Some(counter) = transferred_bytes(&functionality).await {
+                let charged = network_policy.charged_network(&network);
+                dirty |= state
+                    .metered_usage
+                    .record(charged, counter, now.date_naive());
+            }
+        }
+        let network_blocked =
+            network_policy.blocked_reason(&network, &state.metered_usage, state.network_blocked);
+
+        let mut blocking = Vec::new();
+        if !schedule.sync_allowed_at(now.naive_local()) {
+            blocking.push(schedule.action);
+        }
+        if battery_low {
+            blocking.push(config.battery_policy.action);
+        }
+        if network_blocked.is_some() {
+            blocking.push(network_policy.action);
+        }
+
+        let mut changed =
+            state.battery_low != battery_low || state.network_blocked != network_blocked.is_some();
+        state.battery_low = battery_low;
+        state.network_blocked = network_blocked.is_some();
+        changed |= enforce(
+            &functionality,
+            &config,
+            &mut state,
+            blocking.into_iter().max(),
+        )
+        .await;
+        if changed || dirty {
+            save_state(state_path.as_ref(), &state).await;
+        }
+
+        let policy_status = PolicyStatus {
+            power: Some(config.battery_policy.status(power.as_ref(), battery_low)),
+            network: Some(network_policy.status(&network, &state.metered_usage, network_blocked)),
+        };
+        let mut backend = functionality.backend.lock().await;
+        if backend.policy_status != policy_status {
+            backend.policy_status = policy_status;
+            changed = true;
+        }
+        if changed {
+            backend.send_status(&functionality, "sync-policy").await;
+        }
+    }
+}
+
+/// Reads Syncthing's traffic counter, or None while Syncthing is unreachable.
+async fn transferred_bytes(functionality: &BackendReplier<Backend>) -> Option<u64> {
+    let mut backend = functionality.backend.lock().await;
+    let client = backend.client.as_mut()?;
+    match client.transferred_bytes().await {
+        Ok(counter) => Some(counter),
+        Err(err) => {
+            debug!(error = ?err, "Failed to read traffic counters");
+            None
+        }
+    }
+}
+
+/// Moves from the current suspension to the desired one. Returns whether
+/// anything changed; whatever failed is retried on the next tick.
+///
+/// Switching between actions never lets folders sync in between: paused
+/// folders stay paused while the service is stopped and after it is started
+/// again.
+async fn enforce(
+    functionality: &BackendReplier<Backend>,
+    config: &Config,
+    state: &mut SchedulerState,
+    desired: Option<SuspendAction>,
+) -> bool {
+    let before = state.suspended;
+    match (before, desired) {
+        (current, desired) if current == desired => return false,
+        (None, Some(action)) => {
+            suspend(functionality, config, state, action).await;
+        }
+        (Some(current), None) => {
+            resume(functionality, config, state, current).await;
+        }
+        (Some(SuspendAction::PauseFolders), Some(SuspendAction::StopService)) => {
+            if !stop_service(config).await {
+                return false;
+            }
+            state.suspended = Some(SuspendAction::StopService);
+        }
+        (Some(SuspendAction::StopService), Some(SuspendAction::PauseFolders)) => {
+            // Stays stopped on paper until every folder is paused, so a
+            // failure starts the service again and retries on the next tick
+            if !start_service(config).await || !pause_folders(functionality, state).await {
+                return false;
+            }
+            state.suspended = Some(SuspendAction::PauseFolders);
+        }
+        _ => {}
+    }
+    state.suspended != before
+}
+
+/// Suspends sync as configured. Before stopping the service its folders are
+/// paused if Syncthing is reachable, so they stay paused if it starts again.
+async fn suspend(
+    functionality: &BackendReplier<Backend>,
+    config: &Config,
+    state: &mut SchedulerState,
+    action: SuspendAction,
+) {
+    match action {
+        SuspendAction::PauseFolders => {
+            if !pause_folders(functionality, state).await {
+                return;
+            }
+        }
+        SuspendAction::StopService => {
+            pause_folders(functionality, state).await;
+            if !stop_service(config).await {
+                return;
+            }
+        }
+    }
+    state.suspended = Some(action);
+}
+
+/// Undoes what `suspend` did. Folders that cannot be resumed yet because
+/// Syncthing is still starting are resumed on the next tick.
+async fn resume(
+    functionality: &BackendReplier<Backend>,
+    config: &Config,
+    state: &mut SchedulerState,
+    action: SuspendAction,
+) {
+    if action == SuspendAction::StopService {
+        if !start_service(config).await {
+            return;
+        }
+        state.suspended = Some(SuspendAction::PauseFolders);
+    }
+    if resume_folders(functionality, state).await {
+        state.suspended = None;
+    }
+}
+
+/// Pauses all active folders and adds them to `paused_folders`. Returns
+/// false if Syncthing could not be reached.
+async fn pause_folders(
+    functionality: &BackendReplier<Backend>,
+    state: &mut SchedulerState,
+) -> bool {
+    let mut backend = functionality.backend.lock().await;
+    let Some(client) = backend.client.as_mut() else {
+        return false;
+    };
+    let folder_ids = match client.active_folder_ids().await {
+        Ok(folder_ids) => folder_ids,
+        Err(err) => {
+            warn!(error = ?err, "Failed to list folders to pause");
+            return false;
+        }
+    };
+    let mut paused = 0;
+    for folder_id in folder_ids {
+        match client.set_folder_paused(&folder_id, true).await {
+            Ok(()) => {
+                paused += 1;
+                if !state.paused_folders.contains(&folder_id) {
+                    state.paused_folders.push(folder_id);
+                }
+            }
+            Err(err) => warn!(folder = %folder_id, error = ?err, "Failed to pause folder"),
+        }
+    }
+    info!(count = paused, "Paused folders while sync is suspended");
+    true
+}
+
+/// Resumes the folders in `paused_folders`. Returns false if any is still paused.
+async fn resume_folders(
+    functionality: &BackendReplier<Backend>,
+    state: &mut SchedulerState,
+) -> bool {
+    if state.paused_folders.is_empty() {
+        return true;
+    }
+    let mut backend = functionality.backend.lock().await;
+    let Some(client) = backend.client.as_mut() else {
+        return false;
+    };
+    let mut failed = Vec::new();
+    for folder_id in state.paused_folders.drain(..) {
+        if let Err(err) = client.set_folder_paused(&folder_id, false).await {
+            warn!(folder = %folder_id, error = ?err, "Failed to resume folder");
+            failed.push(folder_id);
+        }
+    }
+    if !failed.is_empty() {
+        state.paused_folders = failed;
+        return false;
+    }
+    info!("Resumed folders");
+    true
+}
+
+async fn stop_service(config: &Config) -> bool {
+    if let Err(err) = control_service(config, ServiceAction::Stop).await {
+        warn!(error = ?err, "Failed to stop Syncthing to suspend sync");
+        return false;
+    }
+    info!("Stopped Syncthing while sync is suspended");
+    true
+}
+
+async fn start_service(config: &Config) -> bool {
+    if let Err(err) = control_service(config, ServiceAction::Start).await {
+        warn!(error = ?err, "Failed to start Syncthing to resume sync");
+        return false;
+    }
+    info!("Started Syncthing to resume sync");
+    true
+}
+
+async fn save_state(path: Option<&PathBuf>, state: &SchedulerState) {
+    if let Some(path) = path {
+        if let Err(err) = state.save(path).await {
+            warn!(error = ?err, "Failed to save scheduler state");
+        }
+    }
+}
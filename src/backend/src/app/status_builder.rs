@@ -1,58 +1,73 @@
-use chrono::{SecondsFormat, Utc};
+use chrono::{Local, SecondsFormat, Utc};
 use tracing::warn;
 
 use crate::config::Config;
-use crate::syncthing_client::SyncthingClient;
+use crate::scheduler::{schedule_status, PolicyStatus};
+use crate::syncthing_client::{
+    ConnectionTracker, GuiSecurityPayload, SyncthingClient, SyncthingData,
+};
 use crate::systemd::query_status;
-use crate::types::{MonitorError, StatusPayload, SyncthingOverview};
+use crate::types::{MonitorError, StatusPayload};
 
 /// Builds a complete status payload by aggregating data from multiple sources.
 ///
 /// This orchestrates:
 /// - SystemD service status
 /// - Syncthing client initialization and data collection
+/// - Connection state, from consecutive failures and API URL failover
+/// - GUI address and login/HTTPS state
 /// - Error handling and fallback values
 pub async fn build_status_payload(
     config: &Config,
     client_slot: &mut Option<SyncthingClient>,
+    connection: &mut ConnectionTracker,
+    policy: PolicyStatus,
     reason: &str,
 ) -> StatusPayload {
     let timestamp = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
     let systemd = query_status(config).await;
 
-    let (syncthing, folders, peers, gui_address) = match ensure_client(config, client_slot).await {
+    let (data, gui_settings) = match ensure_client(config, client_slot).await {
         Ok(client) => {
-            let gui_addr = client.get_gui_address().await.ok();
+            let gui_settings = client.gui_settings().await.ok();
             match client.compose_payload().await {
-                Ok(payload) => (payload.overview, payload.folders, payload.peers, gui_addr),
+                Ok(payload) => {
+                    connection.record_success();
+                    (payload, gui_settings)
+                }
                 Err(err) => {
                     warn!(error = ?err, "Collecting payload failed");
+                    connection.record_failure(&err);
                     *client_slot = None;
-                    (
-                        SyncthingOverview::error(err.to_string()),
-                        Vec::new(),
-                        Vec::new(),
-                        None,
-                    )
+                    (SyncthingData::error(err.to_string()), None)
                 }
             }
         }
-        Err(err) => (
-            SyncthingOverview::error(err.to_string()),
-            Vec::new(),
-            Vec::new(),
-            None,
-        ),
+        Err(err) => {
+            connection.record_failure(&err);
+            (SyncthingData::error(err.to_string()), None)
+        }
     };
+    let connection = connection.status(client_slot.as_ref());
 
     StatusPayload {
         fetched_at: timestamp,
         reason: reason.to_string(),
         systemd,
-        syncthing,
-        folders,
-        peers,
-        gui_address,
+        syncthing: data.overview,
+        connection,
+        folders: data.folders,
+        peers: data.peers,
+        pending_devices: data.pending_devices,
+        pending_folders: data.pending_folders,
+        gui_address: gui_settings
+            .as_ref()
+            .map(|gui| gui.address.clone())
+            .filter(|address| !address.is_empty()),
+        gui_security: gui_settings.as_ref().map(GuiSecurityPayload::from),
+        schedule: schedule_status(&config.sync_schedule, Local::now()),
+        power: policy.power,
+        network: policy.network,
     }
 }
 
@@ -3,5 +3,5 @@ mod paths;
 mod types;
 
 // Re-export the main Config type
-pub use types::Config;
+pub use types::{BatteryPolicy, Config, NetworkPolicy, SuspendAction, SyncSchedule, SyncWindow};
 
@@ -12,6 +12,12 @@ impl Config {
         format!("{}/config.xml", dir)
     }
 
+    /// Get the full path to the certificate Syncthing's GUI and API serve
+    pub fn syncthing_gui_cert_path(&self) -> String {
+        let dir = self.syncthing_config_dir.trim_end_matches('/');
+        format!("{}/https-cert.pem", dir)
+    }
+
     /// Get the root directory of the application
     pub fn app_root_dir() -> Result<PathBuf, MonitorError> {
         let config_path = get_config_path()?;
@@ -30,6 +36,16 @@ impl Config {
     pub fn syncthing_binary_path(&self) -> Result<PathBuf, MonitorError> {
         Ok(Self::app_root_dir()?.join("syncthing"))
     }
+
+    /// Get the path to the file recording what the sync scheduler suspended
+    pub fn scheduler_state_path() -> Result<PathBuf, MonitorError> {
+        Ok(Self::app_root_dir()?.join("scheduler-state.json"))
+    }
+
+    /// Get the path to the on-disk change journal
+    pub fn change_journal_path() -> Result<PathBuf, MonitorError> {
+        Ok(Self::app_root_dir()?.join("change-journal.jsonl"))
+    }
 }
 
 /// Get the path to the config.json file
@@ -1,3 +1,6 @@
+use std::collections::BTreeMap;
+
+use chrono::Weekday;
 use serde::{Deserialize, Serialize};
 
 /// Configuration for the Syncthing monitor application
@@ -11,6 +14,135 @@ pub struct Config {
 
     #[serde(default)]
     pub disable_syncthing_installer: bool,
+
+    #[serde(default)]
+    pub sync_schedule: SyncSchedule,
+
+    #[serde(default)]
+    pub battery_policy: BatteryPolicy,
+
+    #[serde(default)]
+    pub network_policy: NetworkPolicy,
+}
+
+/// Times of day during which Syncthing may sync
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SyncSchedule {
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default)]
+    pub action: SuspendAction,
+
+    #[serde(default)]
+    pub windows: Vec<SyncWindow>,
+}
+
+/// How sync is suspended while a policy disallows it. When several policies
+/// apply at once, the stronger action wins.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum SuspendAction {
+    /// Pause all folders through the Syncthing API
+    #[default]
+    PauseFolders,
+    /// Stop the Syncthing systemd service
+    StopService,
+}
+
+/// A daily time range in local time, as `HH:MM`. A window whose `end` is not
+/// after its `start` runs past midnight into the next day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncWindow {
+    /// Days the window starts on; empty means every day
+    #[serde(default)]
+    pub days: Vec<Weekday>,
+
+    pub start: String,
+
+    pub end: String,
+}
+
+/// Suspends sync while the battery is low and not charging
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatteryPolicy {
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default)]
+    pub action: SuspendAction,
+
+    /// Suspend once the charge drops below this percentage
+    #[serde(default = "default_suspend_below_percent")]
+    pub suspend_below_percent: u8,
+
+    /// Resume once the charge reaches this percentage, or when charging
+    #[serde(default = "default_resume_above_percent")]
+    pub resume_above_percent: u8,
+
+    /// Directory holding the kernel's power supply entries
+    #[serde(default = "default_power_supply_dir")]
+    pub power_supply_dir: String,
+}
+
+impl Default for BatteryPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            action: SuspendAction::default(),
+            suspend_below_percent: default_suspend_below_percent(),
+            resume_above_percent: default_resume_above_percent(),
+            power_supply_dir: default_power_supply_dir(),
+        }
+    }
+}
+
+/// Restricts sync to allowlisted networks, with daily budgets on metered ones
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkPolicy {
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default)]
+    pub action: SuspendAction,
+
+    /// Networks sync may use, by Wi-Fi SSID or `usb`; empty allows any network
+    #[serde(default)]
+    pub allowed_networks: Vec<String>,
+
+    /// Daily budgets in MiB for metered networks, by Wi-Fi SSID or `usb`
+    #[serde(default)]
+    pub metered_networks: BTreeMap<String, u64>,
+
+    /// Directory holding the kernel's network interface entries
+    #[serde(default = "default_net_class_dir")]
+    pub net_class_dir: String,
+
+    /// The kernel's IPv4 route table
+    #[serde(default = "default_route_table_path")]
+    pub route_table_path: String,
+
+    #[serde(default = "default_usb_interface")]
+    pub usb_interface: String,
+
+    /// Command printing the connected SSID as `ssid=...` or `SSID: ...`
+    #[serde(default = "default_ssid_command")]
+    pub ssid_command: Vec<String>,
+}
+
+impl Default for NetworkPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            action: SuspendAction::default(),
+            allowed_networks: Vec::new(),
+            metered_networks: BTreeMap::new(),
+            net_class_dir: default_net_class_dir(),
+            route_table_path: default_route_table_path(),
+            usb_interface: default_usb_interface(),
+            ssid_command: default_ssid_command(),
+        }
+    }
 }
 
 impl Default for Config {
@@ -19,6 +151,9 @@ impl Default for Config {
             systemd_service_name: default_service_name(),
             syncthing_config_dir: default_config_dir(),
             disable_syncthing_installer: false,
+            sync_schedule: SyncSchedule::default(),
+            battery_policy: BatteryPolicy::default(),
+            network_policy: NetworkPolicy::default(),
         }
     }
 }
@@ -31,3 +166,30 @@ fn default_config_dir() -> String {
     "/home/root/.config/syncthing".to_string()
 }
 
+fn default_suspend_below_percent() -> u8 {
+    20
+}
+
+fn default_resume_above_percent() -> u8 {
+    30
+}
+
+fn default_power_supply_dir() -> String {
+    "/sys/class/power_supply".to_string()
+}
+
+fn default_net_class_dir() -> String {
+    "/sys/class/net".to_string()
+}
+
+fn default_route_table_path() -> String {
+    "/proc/net/route".to_string()
+}
+
+fn default_usb_interface() -> String {
+    "usb0".to_string()
+}
+
+fn default_ssid_command() -> Vec<String> {
+    vec!["wpa_cli".to_string(), "status".to_string()]
+}
@@ -1,6 +1,9 @@
 mod app;
 mod config;
 mod deployment;
+mod network;
+mod power;
+mod scheduler;
 mod syncthing_client;
 mod systemd;
 mod types;
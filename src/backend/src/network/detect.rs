@@ -0,0 +1,264 @@
+use std::net::Ipv4Addr;
+use std::path::Path;
+
+use tokio::fs;
+use tokio::process::Command;
+use tokio::time::{timeout, Duration};
+use tracing::debug;
+
+use crate::config::NetworkPolicy;
+
+use super::NetworkState;
+
+/// Address range of the USB network the reMarkable offers to a computer
+const USB_NETWORK_PREFIX: [u8; 3] = [10, 11, 99];
+const SSID_COMMAND_TIMEOUT_SECS: u64 = 5;
+
+/// Detects the connected networks from the sources in the network policy.
+pub async fn detect_network(policy: &NetworkPolicy) -> NetworkState {
+    let net_class_dir = Path::new(&policy.net_class_dir);
+    let routes = read_routes(Path::new(&policy.route_table_path)).await;
+
+    let active_interface = routes
+        .iter()
+        .filter(|route| route.destination.is_unspecified() && route.mask.is_unspecified())
+        .min_by_key(|route| route.metric)
+        .map(|route| route.interface.clone());
+
+    let usb_up = interface_up(net_class_dir, &policy.usb_interface).await;
+    let usb_connected = usb_up
+        && routes.iter().any(|route| {
+            route.interface == policy.usb_interface
+                && route.destination.octets()[..3] == USB_NETWORK_PREFIX
+        });
+
+    let wifi_interface = find_wifi_interface(net_class_dir).await;
+    let ssid = match &wifi_interface {
+        Some(_) => read_ssid(&policy.ssid_command).await,
+        None => None,
+    };
+
+    NetworkState {
+        active_interface,
+        wifi_interface,
+        ssid,
+        usb_connected,
+        usb_interface: policy.usb_interface.clone(),
+    }
+}
+
+struct Route {
+    interface: String,
+    destination: Ipv4Addr,
+    mask: Ipv4Addr,
+    metric: u32,
+}
+
+/// Parses `/proc/net/route`. Addresses are printed as the hex value of the
+/// raw network-order word, so its native-endian bytes are the octets.
+async fn read_routes(path: &Path) -> Vec<Route> {
+    let contents = match fs::read_to_string(path).await {
+        Ok(contents) => contents,
+        Err(err) => {
+            debug!(path = %path.display(), error = ?err, "Failed to read route table");
+            return Vec::new();
+        }
+    };
+    contents.lines().skip(1).filter_map(parse_route).collect()
+}
+
+fn parse_route(line: &str) -> Option<Route> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() < 8 {
+        return None;
+    }
+    Some(Route {
+        interface: fields[0].to_string(),
+        destination: parse_hex_address(fields[1])?,
+        metric: fields[6].parse().ok()?,
+        mask: parse_hex_address(fields[7])?,
+    })
+}
+
+fn parse_hex_address(value: &str) -> Option<Ipv4Addr> {
+    let raw = u32::from_str_radix(value, 16).ok()?;
+    Some(Ipv4Addr::from(raw.to_ne_bytes()))
+}
+
+async fn interface_up(net_class_dir: &Path, interface: &str) -> bool {
+    match fs::read_to_string(net_class_dir.join(interface).join("operstate")).await {
+        // Gadget interfaces often report "unknown" while working fine
+        Ok(state) => matches!(state.trim(), "up" | "unknown"),
+        Err(_) => false,
+    }
+}
+
+/// Finds a wireless interface that is up.
+async fn find_wifi_interface(net_class_dir: &Path) -> Option<String> {
+    let mut entries = fs::read_dir(net_class_dir).await.ok()?;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let is_wireless = fs::metadata(entry.path().join("wireless")).await.is_ok();
+        if is_wireless && interface_up(net_class_dir, &name).await {
+            return Some(name);
+        }
+    }
+    None
+}
+
+/// Runs the SSID command and reads the SSID from `wpa_cli status`
+/// (`ssid=...`) or `iw dev <iface> link` (`SSID: ...`) output.
+async fn read_ssid(command: &[String]) -> Option<String> {
+    let (program, args) = command.split_first()?;
+    let output = timeout(
+        Duration::from_secs(SSID_COMMAND_TIMEOUT_SECS),
+        Command::new(program).args(args).output(),
+    )
+    .await;
+    let output = match output {
+        Ok(Ok(output)) if output.status.success() => output,
+        Ok(Ok(output)) => {
+            debug!(status = %output.status, "SSID command failed");
+            return None;
+        }
+        Ok(Err(err)) => {
+            debug!(error = ?err, "Failed to run SSID command");
+            return None;
+        }
+        Err(_) => {
+            debug!("SSID command timed out");
+            return None;
+        }
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .find_map(|line| {
+            line.strip_prefix("ssid=")
+                .or_else(|| line.strip_prefix("SSID: "))
+        })
+        .map(|ssid| ssid.to_string())
+        .filter(|ssid| !ssid.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const ROUTE_HEADER: &str =
+        "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT";
+
+    /// Formats an address the way the kernel prints it in `/proc/net/route`.
+    fn hex(octets: [u8; 4]) -> String {
+        format!("{:08X}", u32::from_ne_bytes(octets))
+    }
+
+    fn route_line(interface: &str, destination: [u8; 4], metric: u32, mask: [u8; 4]) -> String {
+        format!(
+            "{interface}\t{}\t00000000\t0001\t0\t0\t{metric}\t{}\t0\t0\t0",
+            hex(destination),
+            hex(mask)
+        )
+    }
+
+    fn interface(net_class_dir: &Path, name: &str, operstate: &str, wireless: bool) {
+        let dir = net_class_dir.join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("operstate"), format!("{operstate}\n")).unwrap();
+        if wireless {
+            fs::create_dir(dir.join("wireless")).unwrap();
+        }
+    }
+
+    fn policy(root: &TempDir, routes: &[String]) -> NetworkPolicy {
+        let net_class_dir = root.path().join("net");
+        fs::create_dir(&net_class_dir).unwrap();
+        let route_table_path = root.path().join("route");
+        let mut table = vec![ROUTE_HEADER.to_string()];
+        table.extend_from_slice(routes);
+        fs::write(&route_table_path, table.join("\n")).unwrap();
+        NetworkPolicy {
+            net_class_dir: net_class_dir.to_string_lossy().to_string(),
+            route_table_path: route_table_path.to_string_lossy().to_string(),
+            usb_interface: "usb0".to_string(),
+            ssid_command: vec!["echo".to_string(), "ssid=Home".to_string()],
+            ..NetworkPolicy::default()
+        }
+    }
+
+    #[test]
+    fn parses_route_line() {
+        let route = parse_route(&route_line(
+            "usb0",
+            [10, 11, 99, 0],
+            5,
+            [255, 255, 255, 248],
+        ))
+        .unwrap();
+        assert_eq!(route.interface, "usb0");
+        assert_eq!(route.destination, Ipv4Addr::new(10, 11, 99, 0));
+        assert_eq!(route.mask, Ipv4Addr::new(255, 255, 255, 248));
+        assert_eq!(route.metric, 5);
+    }
+
+    #[test]
+    fn rejects_malformed_route_lines() {
+        assert!(parse_route("wlan0\t00000000\t0101A8C0").is_none());
+        assert!(parse_route("wlan0\tnothex\t00000000\t0003\t0\t0\t0\t00000000").is_none());
+    }
+
+    #[tokio::test]
+    async fn detects_usb_network_and_default_route() {
+        let root = TempDir::new().unwrap();
+        let policy = policy(
+            &root,
+            &[
+                route_line("wlan0", [0, 0, 0, 0], 600, [0, 0, 0, 0]),
+                route_line("usb0", [0, 0, 0, 0], 100, [0, 0, 0, 0]),
+                route_line("usb0", [10, 11, 99, 0], 0, [255, 255, 255, 248]),
+                route_line("wlan0", [192, 168, 1, 0], 600, [255, 255, 255, 0]),
+            ],
+        );
+        let net_class_dir = Path::new(&policy.net_class_dir);
+        interface(net_class_dir, "usb0", "unknown", false);
+        interface(net_class_dir, "wlan0", "up", true);
+
+        let state = detect_network(&policy).await;
+        assert_eq!(state.active_interface.as_deref(), Some("usb0"));
+        assert_eq!(state.wifi_interface.as_deref(), Some("wlan0"));
+        assert_eq!(state.ssid.as_deref(), Some("Home"));
+        assert!(state.usb_connected);
+    }
+
+    #[tokio::test]
+    async fn usb_interface_outside_usb_network_is_not_connected() {
+        let root = TempDir::new().unwrap();
+        let policy = policy(
+            &root,
+            &[route_line("usb0", [192, 168, 7, 0], 0, [255, 255, 255, 0])],
+        );
+        interface(Path::new(&policy.net_class_dir), "usb0", "up", false);
+
+        let state = detect_network(&policy).await;
+        assert!(!state.usb_connected);
+        assert_eq!(state.active_interface, None);
+        assert_eq!(state.wifi_interface, None);
+        assert_eq!(state.ssid, None);
+    }
+
+    #[tokio::test]
+    async fn usb_interface_down_is_not_connected() {
+        let root = TempDir::new().unwrap();
+        let policy = policy(
+            &root,
+            &[route_line("usb0", [10, 11, 99, 0], 0, [255, 255, 255, 248])],
+        );
+        interface(Path::new(&policy.net_class_dir), "usb0", "down", false);
+
+        assert!(!detect_network(&policy).await.usb_connected);
+    }
+}
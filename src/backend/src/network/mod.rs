@@ -0,0 +1,6 @@
+mod detect;
+mod policy;
+mod types;
+
+pub use detect::detect_network;
+pub use types::{MeteredNetworkStatus, MeteredUsage, NetworkState, NetworkStatus};
@@ -0,0 +1,97 @@
+use crate::config::NetworkPolicy;
+
+use super::{MeteredNetworkStatus, MeteredUsage, NetworkState, NetworkStatus};
+
+const BYTES_PER_MIB: u64 = 1024 * 1024;
+
+impl NetworkPolicy {
+    fn allows(&self, network: &str) -> bool {
+        self.allowed_networks.is_empty() || self.allowed_networks.iter().any(|n| n == network)
+    }
+
+    fn budget_bytes(&self, network: &str) -> Option<u64> {
+        self.metered_networks
+            .get(network)
+            .map(|mib| mib.saturating_mul(BYTES_PER_MIB))
+    }
+
+    fn budget_left(&self, network: &str, usage: &MeteredUsage) -> bool {
+        self.budget_bytes(network)
+            .map(|budget| usage.used(network) < budget)
+            .unwrap_or(true)
+    }
+
+    /// The metered network current traffic is charged to, if any.
+    pub fn charged_network<'a>(&self, state: &'a NetworkState) -> Option<&'a str> {
+        if !self.enabled {
+            return None;
+        }
+        state
+            .active_network()
+            .filter(|network| self.budget_bytes(network).is_some())
+    }
+
+    /// Why sync must stay suspended on the current networks, or None if it may run.
+    ///
+    /// Sync may run while the network carrying the default route is
+    /// allowlisted and has budget left. Only when that network is unknown does
+    /// any connected network that qualifies allow it. While offline the
+    /// previous decision stands, so sync is not paused and resumed every time
+    /// Wi-Fi goes to sleep.
+    pub fn blocked_reason(
+        &self,
+        state: &NetworkState,
+        usage: &MeteredUsage,
+        was_blocked: bool,
+    ) -> Option<String> {
+        if !self.enabled {
+            return None;
+        }
+        let networks = match state.active_network() {
+            Some(active) => vec![active],
+            None => state.networks(),
+        };
+        if networks.is_empty() {
+            return was_blocked.then(|| "No network connection".to_string());
+        }
+        if networks
+            .iter()
+            .any(|network| self.allows(network) && self.budget_left(network, usage))
+        {
+            return None;
+        }
+        let exhausted = networks
+            .iter()
+            .find(|network| self.allows(network) && !self.budget_left(network, usage));
+        Some(match exhausted {
+            Some(network) => format!("Daily budget for {network} used up"),
+            None => format!("{} is not an allowed network", networks.join(", ")),
+        })
+    }
+
+    pub fn status(
+        &self,
+        state: &NetworkState,
+        usage: &MeteredUsage,
+        blocked_reason: Option<String>,
+    ) -> NetworkStatus {
+        let metered = self
+            .metered_networks
+            .keys()
+            .filter_map(|network| {
+                Some(MeteredNetworkStatus {
+                    network: network.clone(),
+                    used_bytes: usage.used(network),
+                    budget_bytes: self.budget_bytes(network)?,
+                })
+            })
+            .collect();
+        NetworkStatus {
+            state: state.clone(),
+            policy_enabled: self.enabled,
+            sync_blocked: blocked_reason.is_some(),
+            blocked_reason,
+            metered,
+        }
+    }
+}
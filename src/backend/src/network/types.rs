@@ -0,0 +1,193 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Name under which the USB network is listed in the network policy
+pub const USB_NETWORK: &str = "usb";
+
+/// The networks the device is currently connected to
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct NetworkState {
+    /// Interface carrying the default route
+    pub active_interface: Option<String>,
+    pub wifi_interface: Option<String>,
+    /// SSID of the connected Wi-Fi network
+    pub ssid: Option<String>,
+    /// Whether the USB network (10.11.99.x) is up
+    pub usb_connected: bool,
+    #[serde(skip)]
+    pub usb_interface: String,
+}
+
+impl NetworkState {
+    /// Names of the connected networks, as used in the network policy
+    pub fn networks(&self) -> Vec<&str> {
+        let mut networks = Vec::new();
+        if let Some(ssid) = &self.ssid {
+            networks.push(ssid.as_str());
+        }
+        if self.usb_connected {
+            networks.push(USB_NETWORK);
+        }
+        networks
+    }
+
+    /// The network most traffic goes through: the one carrying the default
+    /// route, or the only connected one.
+    pub fn active_network(&self) -> Option<&str> {
+        match self.active_interface.as_deref() {
+            Some(iface) if self.usb_connected && iface == self.usb_interface => Some(USB_NETWORK),
+            Some(iface) if self.wifi_interface.as_deref() == Some(iface) => self.ssid.as_deref(),
+            _ => match self.networks().as_slice() {
+                [only] => Some(only),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Bytes Syncthing transferred on each metered network today
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MeteredUsage {
+    #[serde(default)]
+    pub day: Option<NaiveDate>,
+
+    #[serde(default)]
+    pub bytes: BTreeMap<String, u64>,
+
+    /// Last reading of Syncthing's traffic counter
+    #[serde(skip)]
+    last_counter: Option<u64>,
+}
+
+impl MeteredUsage {
+    /// Charges the traffic since the last reading to `network`. Returns
+    /// whether the recorded usage changed.
+    pub fn record(&mut self, network: Option<&str>, counter: u64, today: NaiveDate) -> bool {
+        let mut changed = false;
+        if self.day != Some(today) {
+            changed = !self.bytes.is_empty();
+            self.day = Some(today);
+            self.bytes.clear();
+        }
+        // The counter restarts from zero along with Syncthing
+        let delta = match self.last_counter {
+            Some(last) => counter.checked_sub(last).unwrap_or(counter),
+            None => 0,
+        };
+        self.last_counter = Some(counter);
+        if let Some(network) = network.filter(|_| delta > 0) {
+            *self.bytes.entry(network.to_string()).or_default() += delta;
+            changed = true;
+        }
+        changed
+    }
+
+    pub fn used(&self, network: &str) -> u64 {
+        self.bytes.get(network).copied().unwrap_or(0)
+    }
+}
+
+/// Network state and policy, for the status payload.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct NetworkStatus {
+    #[serde(flatten)]
+    pub state: NetworkState,
+    pub policy_enabled: bool,
+    /// Whether the network policy currently holds sync suspended
+    pub sync_blocked: bool,
+    pub blocked_reason: Option<String>,
+    pub metered: Vec<MeteredNetworkStatus>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct MeteredNetworkStatus {
+    pub network: String,
+    pub used_bytes: u64,
+    pub budget_bytes: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(
+        active_interface: Option<&str>,
+        ssid: Option<&str>,
+        usb_connected: bool,
+    ) -> NetworkState {
+        NetworkState {
+            active_interface: active_interface.map(str::to_string),
+            wifi_interface: ssid.map(|_| "wlan0".to_string()),
+            ssid: ssid.map(str::to_string),
+            usb_connected,
+            usb_interface: "usb0".to_string(),
+        }
+    }
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+    }
+
+    #[test]
+    fn active_network_follows_default_route() {
+        let both = state(Some("wlan0"), Some("Home"), true);
+        assert_eq!(both.networks(), vec!["Home", USB_NETWORK]);
+        assert_eq!(both.active_network(), Some("Home"));
+
+        let both = state(Some("usb0"), Some("Home"), true);
+        assert_eq!(both.active_network(), Some(USB_NETWORK));
+    }
+
+    #[test]
+    fn active_network_without_default_route() {
+        assert_eq!(state(None, None, true).active_network(), Some(USB_NETWORK));
+        assert_eq!(
+            state(None, Some("Home"), false).active_network(),
+            Some("Home")
+        );
+        assert_eq!(state(None, Some("Home"), true).active_network(), None);
+        assert_eq!(state(None, None, false).active_network(), None);
+    }
+
+    #[test]
+    fn active_network_ignores_disconnected_usb_route() {
+        let state = state(Some("usb0"), Some("Home"), false);
+        assert_eq!(state.active_network(), Some("Home"));
+    }
+
+    #[test]
+    fn records_traffic_since_last_reading() {
+        let mut usage = MeteredUsage::default();
+        assert!(!usage.record(Some("Phone"), 1_000, day(1)));
+        assert_eq!(usage.used("Phone"), 0);
+
+        assert!(usage.record(Some("Phone"), 1_500, day(1)));
+        assert!(!usage.record(None, 1_700, day(1)));
+        assert!(usage.record(Some("Phone"), 2_000, day(1)));
+        assert_eq!(usage.used("Phone"), 800);
+    }
+
+    #[test]
+    fn clears_usage_on_new_day() {
+        let mut usage = MeteredUsage::default();
+        usage.record(Some("Phone"), 0, day(1));
+        usage.record(Some("Phone"), 500, day(1));
+
+        assert!(usage.record(Some("Phone"), 600, day(2)));
+        assert_eq!(usage.day, Some(day(2)));
+        assert_eq!(usage.used("Phone"), 100);
+    }
+
+    #[test]
+    fn counter_reset_counts_from_zero() {
+        let mut usage = MeteredUsage::default();
+        usage.record(Some("Phone"), 0, day(1));
+        usage.record(Some("Phone"), 5_000, day(1));
+
+        // Syncthing restarted, so its counter started over
+        assert!(usage.record(Some("Phone"), 300, day(1)));
+        assert_eq!(usage.used("Phone"), 5_300);
+    }
+}
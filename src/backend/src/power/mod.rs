@@ -0,0 +1,6 @@
+mod policy;
+mod reader;
+mod types;
+
+pub use reader::read_power_supply;
+pub use types::{BatteryPolicyStatus, PowerReading, PowerStatus};
@@ -0,0 +1,106 @@
+use crate::config::BatteryPolicy;
+
+use super::{BatteryPolicyStatus, PowerReading, PowerStatus};
+
+impl BatteryPolicy {
+    /// Whether the battery is low enough to hold sync suspended.
+    ///
+    /// Between the two thresholds the previous decision stands, so sync does
+    /// not flap while the charge hovers around a single value.
+    pub fn battery_low(&self, reading: Option<&PowerReading>, was_low: bool) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let Some(reading) = reading else {
+            return false;
+        };
+        let Some(capacity) = reading.capacity_percent else {
+            return false;
+        };
+        if reading.charging {
+            return false;
+        }
+        if capacity < self.suspend_below_percent {
+            true
+        } else if capacity >= self.resume_above_percent.max(self.suspend_below_percent) {
+            false
+        } else {
+            was_low
+        }
+    }
+
+    pub fn status(&self, reading: Option<&PowerReading>, battery_low: bool) -> PowerStatus {
+        PowerStatus {
+            capacity_percent: reading.and_then(|reading| reading.capacity_percent),
+            charging: reading.map(|reading| reading.charging).unwrap_or(false),
+            sync_blocked: battery_low,
+            policy: self.enabled.then_some(BatteryPolicyStatus {
+                action: self.action,
+                suspend_below_percent: self.suspend_below_percent,
+                resume_above_percent: self.resume_above_percent,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> BatteryPolicy {
+        BatteryPolicy {
+            enabled: true,
+            suspend_below_percent: 20,
+            resume_above_percent: 30,
+            ..BatteryPolicy::default()
+        }
+    }
+
+    fn discharging(capacity: u8) -> PowerReading {
+        PowerReading {
+            capacity_percent: Some(capacity),
+            charging: false,
+        }
+    }
+
+    #[test]
+    fn suspends_below_threshold() {
+        assert!(policy().battery_low(Some(&discharging(19)), false));
+        assert!(!policy().battery_low(Some(&discharging(20)), false));
+    }
+
+    #[test]
+    fn keeps_previous_decision_between_thresholds() {
+        let policy = policy();
+        for capacity in 20..30 {
+            assert!(policy.battery_low(Some(&discharging(capacity)), true));
+            assert!(!policy.battery_low(Some(&discharging(capacity)), false));
+        }
+        assert!(!policy.battery_low(Some(&discharging(30)), true));
+    }
+
+    #[test]
+    fn charging_or_unknown_charge_never_suspends() {
+        let policy = policy();
+        let charging = PowerReading {
+            capacity_percent: Some(5),
+            charging: true,
+        };
+        let unknown = PowerReading {
+            capacity_percent: None,
+            charging: false,
+        };
+        assert!(!policy.battery_low(Some(&charging), true));
+        assert!(!policy.battery_low(Some(&unknown), true));
+        assert!(!policy.battery_low(None, true));
+    }
+
+    #[test]
+    fn disabled_policy_never_suspends() {
+        let policy = BatteryPolicy {
+            enabled: false,
+            ..policy()
+        };
+        assert!(!policy.battery_low(Some(&discharging(1)), true));
+    }
+}
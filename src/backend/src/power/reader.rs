@@ -0,0 +1,153 @@
+use std::path::Path;
+
+use tokio::fs;
+
+use crate::types::MonitorError;
+
+use super::PowerReading;
+
+/// Reads the battery from a sysfs power supply directory such as
+/// `/sys/class/power_supply`. Returns None if it holds no battery.
+///
+/// The device counts as charging when the battery reports `Charging` or
+/// `Full`, or when any external supply (USB, mains) is online.
+pub async fn read_power_supply(dir: &Path) -> Result<Option<PowerReading>, MonitorError> {
+    let mut entries = fs::read_dir(dir).await?;
+    let mut battery = None;
+    let mut external_power = false;
+
+    while let Some(entry) = entries.next_entry().await? {
+        let supply = entry.path();
+        let Some(kind) = read_attribute(&supply, "type").await else {
+            continue;
+        };
+        if kind == "Battery" {
+            if battery.is_some() {
+                continue;
+            }
+            let capacity_percent = read_attribute(&supply, "capacity")
+                .await
+                .and_then(|value| value.parse::<u8>().ok())
+                .map(|value| value.min(100));
+            let status = read_attribute(&supply, "status").await.unwrap_or_default();
+            battery = Some((
+                capacity_percent,
+                matches!(status.as_str(), "Charging" | "Full"),
+            ));
+        } else if read_attribute(&supply, "online").await.as_deref() == Some("1") {
+            external_power = true;
+        }
+    }
+
+    Ok(battery.map(|(capacity_percent, charging)| PowerReading {
+        capacity_percent,
+        charging: charging || external_power,
+    }))
+}
+
+async fn read_attribute(supply: &Path, name: &str) -> Option<String> {
+    fs::read_to_string(supply.join(name))
+        .await
+        .ok()
+        .map(|value| value.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn supply(root: &Path, name: &str, attributes: &[(&str, &str)]) {
+        let dir = root.join(name);
+        fs::create_dir(&dir).unwrap();
+        for (attribute, value) in attributes {
+            fs::write(dir.join(attribute), format!("{value}\n")).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn battery_with_online_usb_supply_is_charging() {
+        let root = TempDir::new().unwrap();
+        supply(
+            root.path(),
+            "max77818_battery",
+            &[
+                ("type", "Battery"),
+                ("capacity", "42"),
+                ("status", "Discharging"),
+            ],
+        );
+        supply(
+            root.path(),
+            "max77818-charger",
+            &[("type", "USB"), ("online", "1")],
+        );
+
+        let reading = read_power_supply(root.path()).await.unwrap();
+        assert_eq!(
+            reading,
+            Some(PowerReading {
+                capacity_percent: Some(42),
+                charging: true,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn offline_supply_does_not_count_as_charging() {
+        let root = TempDir::new().unwrap();
+        supply(
+            root.path(),
+            "battery",
+            &[
+                ("type", "Battery"),
+                ("capacity", "80"),
+                ("status", "Discharging"),
+            ],
+        );
+        supply(root.path(), "usb", &[("type", "USB"), ("online", "0")]);
+
+        let reading = read_power_supply(root.path()).await.unwrap().unwrap();
+        assert!(!reading.charging);
+    }
+
+    #[tokio::test]
+    async fn full_battery_counts_as_charging() {
+        let root = TempDir::new().unwrap();
+        supply(
+            root.path(),
+            "battery",
+            &[("type", "Battery"), ("capacity", "100"), ("status", "Full")],
+        );
+
+        let reading = read_power_supply(root.path()).await.unwrap().unwrap();
+        assert!(reading.charging);
+        assert_eq!(reading.capacity_percent, Some(100));
+    }
+
+    #[tokio::test]
+    async fn missing_capacity_is_unknown() {
+        let root = TempDir::new().unwrap();
+        supply(
+            root.path(),
+            "battery",
+            &[("type", "Battery"), ("status", "Discharging")],
+        );
+
+        let reading = read_power_supply(root.path()).await.unwrap().unwrap();
+        assert_eq!(reading.capacity_percent, None);
+        assert!(!reading.charging);
+    }
+
+    #[tokio::test]
+    async fn no_battery_yields_none() {
+        let root = TempDir::new().unwrap();
+        supply(root.path(), "usb", &[("type", "USB"), ("online", "1")]);
+
+        assert_eq!(read_power_supply(root.path()).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,29 @@
+use serde::Serialize;
+
+use crate::config::SuspendAction;
+
+/// Battery charge and charging state as reported by the kernel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PowerReading {
+    pub capacity_percent: Option<u8>,
+    pub charging: bool,
+}
+
+/// Battery state and policy, for the status payload.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct PowerStatus {
+    /// None when no battery could be read
+    pub capacity_percent: Option<u8>,
+    pub charging: bool,
+    /// Whether the battery policy currently holds sync suspended
+    pub sync_blocked: bool,
+    /// None when the battery policy is disabled
+    pub policy: Option<BatteryPolicyStatus>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct BatteryPolicyStatus {
+    pub action: SuspendAction,
+    pub suspend_below_percent: u8,
+    pub resume_above_percent: u8,
+}
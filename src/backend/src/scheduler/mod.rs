@@ -0,0 +1,6 @@
+mod state;
+mod status;
+mod windows;
+
+pub use state::SchedulerState;
+pub use status::{schedule_status, PolicyStatus, ScheduleStatus};
@@ -0,0 +1,57 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tracing::warn;
+
+use crate::config::SuspendAction;
+use crate::network::MeteredUsage;
+use crate::types::MonitorError;
+
+/// What the scheduler suspended, persisted so a restarted backend resumes
+/// exactly that and leaves folders the user paused alone.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SchedulerState {
+    /// How sync was suspended, or None while it is not
+    #[serde(default)]
+    pub suspended: Option<SuspendAction>,
+
+    /// Folders paused by the scheduler
+    #[serde(default)]
+    pub paused_folders: Vec<String>,
+
+    /// Whether the battery policy last found the battery low
+    #[serde(default)]
+    pub battery_low: bool,
+
+    /// Whether the network policy last held sync suspended
+    #[serde(default)]
+    pub network_blocked: bool,
+
+    #[serde(default)]
+    pub metered_usage: MeteredUsage,
+}
+
+impl SchedulerState {
+    /// Load the state, falling back to "nothing suspended" if it is missing or unreadable
+    pub async fn load(path: &Path) -> Self {
+        let contents = match fs::read_to_string(path).await {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Self::default(),
+            Err(err) => {
+                warn!(path = %path.display(), error = ?err, "Failed to read scheduler state");
+                return Self::default();
+            }
+        };
+        serde_json::from_str(&contents).unwrap_or_else(|err| {
+            warn!(path = %path.display(), error = ?err, "Failed to parse scheduler state");
+            Self::default()
+        })
+    }
+
+    pub async fn save(&self, path: &Path) -> Result<(), MonitorError> {
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(path, contents).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use chrono::{DateTime, Local, SecondsFormat};
+use serde::Serialize;
+
+use crate::config::{SuspendAction, SyncSchedule};
+use crate::network::NetworkStatus;
+use crate::power::PowerStatus;
+
+/// Current state of the sync schedule, for the status payload.
+#[derive(Debug, Serialize, Clone)]
+pub struct ScheduleStatus {
+    pub action: SuspendAction,
+    pub sync_allowed: bool,
+    pub next_transition: Option<String>,
+}
+
+/// Latest state of the battery and network policies, kept by the scheduler task
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PolicyStatus {
+    pub power: Option<PowerStatus>,
+    pub network: Option<NetworkStatus>,
+}
+
+/// Describe the schedule at `now`, or None when no schedule is active
+pub fn schedule_status(schedule: &SyncSchedule, now: DateTime<Local>) -> Option<ScheduleStatus> {
+    if !schedule.is_active() {
+        return None;
+    }
+    Some(ScheduleStatus {
+        action: schedule.action,
+        sync_allowed: schedule.sync_allowed_at(now.naive_local()),
+        next_transition: schedule
+            .next_transition(now)
+            .map(|at| at.to_rfc3339_opts(SecondsFormat::Secs, false)),
+    })
+}
@@ -0,0 +1,249 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDateTime, NaiveTime, Weekday};
+
+use crate::config::{SyncSchedule, SyncWindow};
+
+/// Days ahead searched for the next transition; every weekly window repeats within it.
+const TRANSITION_LOOKAHEAD_DAYS: i64 = 8;
+
+/// A sync window with its times parsed.
+struct ParsedWindow<'a> {
+    days: &'a [Weekday],
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl ParsedWindow<'_> {
+    fn starts_on(&self, day: Weekday) -> bool {
+        self.days.is_empty() || self.days.contains(&day)
+    }
+
+    /// Whether the window contains a point in time; the end is exclusive.
+    fn contains(&self, at: NaiveDateTime) -> bool {
+        let time = at.time();
+        let today = at.weekday();
+        if self.start < self.end {
+            self.starts_on(today) && time >= self.start && time < self.end
+        } else {
+            // Runs past midnight; equal start and end cover a full 24 hours
+            (self.starts_on(today) && time >= self.start)
+                || (self.starts_on(today.pred()) && time < self.end)
+        }
+    }
+}
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").ok()
+}
+
+fn parse_window(window: &SyncWindow) -> Option<ParsedWindow<'_>> {
+    Some(ParsedWindow {
+        days: &window.days,
+        start: parse_time(&window.start)?,
+        end: parse_time(&window.end)?,
+    })
+}
+
+impl SyncSchedule {
+    fn parsed_windows(&self) -> Vec<ParsedWindow<'_>> {
+        self.windows.iter().filter_map(parse_window).collect()
+    }
+
+    /// Whether the schedule restricts syncing at all.
+    pub fn is_active(&self) -> bool {
+        self.enabled && !self.parsed_windows().is_empty()
+    }
+
+    /// Describes windows whose times are not valid `HH:MM` values; these are ignored.
+    pub fn invalid_windows(&self) -> Vec<String> {
+        self.windows
+            .iter()
+            .enumerate()
+            .filter(|(_, window)| parse_window(window).is_none())
+            .map(|(index, window)| {
+                format!(
+                    "window {}: invalid time range {}-{}",
+                    index + 1,
+                    window.start,
+                    window.end
+                )
+            })
+            .collect()
+    }
+
+    /// Whether syncing is allowed at the given local time.
+    pub fn sync_allowed_at(&self, at: NaiveDateTime) -> bool {
+        if !self.enabled {
+            return true;
+        }
+        let windows = self.parsed_windows();
+        windows.is_empty() || windows.iter().any(|window| window.contains(at))
+    }
+
+    /// The next time syncing switches between allowed and suspended, if any.
+    pub fn next_transition(&self, now: DateTime<Local>) -> Option<DateTime<Local>> {
+        self.next_transition_with(now.naive_local(), |candidate| {
+            candidate.and_local_timezone(Local).earliest()
+        })
+    }
+
+    /// Finds the next transition after `now`, resolving candidate times with
+    /// `to_local`. Times that do not exist locally, such as those skipped when
+    /// daylight saving time starts, are passed over.
+    fn next_transition_with<T>(
+        &self,
+        now: NaiveDateTime,
+        to_local: impl Fn(NaiveDateTime) -> Option<T>,
+    ) -> Option<T> {
+        if !self.is_active() {
+            return None;
+        }
+        let allowed_now = self.sync_allowed_at(now);
+
+        // Every transition happens at the start or end of some window
+        let mut candidates: Vec<NaiveDateTime> = Vec::new();
+        for offset in -1..=TRANSITION_LOOKAHEAD_DAYS {
+            let date = now.date() + Duration::days(offset);
+            for window in self.parsed_windows() {
+                if !window.starts_on(date.weekday()) {
+                    continue;
+                }
+                let start = date.and_time(window.start);
+                let end = if window.end > window.start {
+                    date.and_time(window.end)
+                } else {
+                    (date + Duration::days(1)).and_time(window.end)
+                };
+                candidates.extend([start, end]);
+            }
+        }
+        candidates.retain(|candidate| *candidate > now);
+        candidates.sort();
+        candidates.dedup();
+
+        candidates
+            .into_iter()
+            .filter(|candidate| self.sync_allowed_at(*candidate) != allowed_now)
+            .find_map(to_local)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn window(days: &[Weekday], start: &str, end: &str) -> SyncWindow {
+        SyncWindow {
+            days: days.to_vec(),
+            start: start.to_string(),
+            end: end.to_string(),
+        }
+    }
+
+    fn schedule(windows: Vec<SyncWindow>) -> SyncSchedule {
+        SyncSchedule {
+            enabled: true,
+            windows,
+            ..SyncSchedule::default()
+        }
+    }
+
+    /// 2024-03-04 is a Monday.
+    fn at(day: u32, time: &str) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 3, day)
+            .unwrap()
+            .and_time(NaiveTime::parse_from_str(time, "%H:%M").unwrap())
+    }
+
+    fn next(schedule: &SyncSchedule, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        schedule.next_transition_with(now, Some)
+    }
+
+    #[test]
+    fn window_within_a_day() {
+        let schedule = schedule(vec![window(&[], "09:00", "17:00")]);
+        assert!(!schedule.sync_allowed_at(at(4, "08:59")));
+        assert!(schedule.sync_allowed_at(at(4, "09:00")));
+        assert!(!schedule.sync_allowed_at(at(4, "17:00")));
+    }
+
+    #[test]
+    fn window_past_midnight() {
+        let schedule = schedule(vec![window(&[], "22:00", "06:00")]);
+        assert!(schedule.sync_allowed_at(at(4, "23:30")));
+        assert!(schedule.sync_allowed_at(at(5, "05:59")));
+        assert!(!schedule.sync_allowed_at(at(5, "06:00")));
+        assert!(!schedule.sync_allowed_at(at(5, "12:00")));
+    }
+
+    #[test]
+    fn equal_start_and_end_cover_a_full_day() {
+        let schedule = schedule(vec![window(&[Weekday::Mon], "08:00", "08:00")]);
+        assert!(!schedule.sync_allowed_at(at(4, "07:59")));
+        assert!(schedule.sync_allowed_at(at(4, "08:00")));
+        assert!(schedule.sync_allowed_at(at(5, "07:59")));
+        assert!(!schedule.sync_allowed_at(at(5, "08:00")));
+        assert_eq!(next(&schedule, at(4, "12:00")), Some(at(5, "08:00")));
+    }
+
+    #[test]
+    fn day_filter_applies_to_the_start_day() {
+        let schedule = schedule(vec![window(&[Weekday::Fri], "22:00", "02:00")]);
+        // Friday night continues into Saturday morning
+        assert!(schedule.sync_allowed_at(at(8, "23:00")));
+        assert!(schedule.sync_allowed_at(at(9, "01:00")));
+        // Thursday night is not part of the window
+        assert!(!schedule.sync_allowed_at(at(7, "23:00")));
+        assert!(!schedule.sync_allowed_at(at(8, "01:00")));
+    }
+
+    #[test]
+    fn disabled_or_empty_schedule_allows_sync() {
+        let mut disabled = schedule(vec![window(&[], "09:00", "17:00")]);
+        disabled.enabled = false;
+        assert!(disabled.sync_allowed_at(at(4, "03:00")));
+        assert_eq!(next(&disabled, at(4, "03:00")), None);
+
+        let invalid = schedule(vec![window(&[], "9am", "17:00")]);
+        assert!(invalid.sync_allowed_at(at(4, "03:00")));
+        assert_eq!(next(&invalid, at(4, "03:00")), None);
+    }
+
+    #[test]
+    fn next_transition_finds_start_and_end() {
+        let schedule = schedule(vec![window(&[], "22:00", "06:00")]);
+        assert_eq!(next(&schedule, at(4, "12:00")), Some(at(4, "22:00")));
+        assert_eq!(next(&schedule, at(4, "22:00")), Some(at(5, "06:00")));
+        assert_eq!(next(&schedule, at(5, "01:00")), Some(at(5, "06:00")));
+    }
+
+    #[test]
+    fn next_transition_skips_adjacent_windows() {
+        let schedule = schedule(vec![
+            window(&[], "08:00", "12:00"),
+            window(&[], "12:00", "18:00"),
+        ]);
+        assert_eq!(next(&schedule, at(4, "09:00")), Some(at(4, "18:00")));
+    }
+
+    #[test]
+    fn next_transition_looks_ahead_a_week() {
+        let schedule = schedule(vec![window(&[Weekday::Mon], "09:00", "10:00")]);
+        assert_eq!(next(&schedule, at(4, "10:30")), Some(at(11, "09:00")));
+    }
+
+    #[test]
+    fn next_transition_passes_over_nonexistent_times() {
+        let schedule = schedule(vec![window(&[], "02:30", "04:00")]);
+        // 02:00-03:00 on Sunday 2024-03-10 is skipped by a DST change
+        let skipped = |time: NaiveDateTime| {
+            let gap = at(10, "02:00")..at(10, "03:00");
+            (!gap.contains(&time)).then_some(time)
+        };
+        assert_eq!(
+            schedule.next_transition_with(at(10, "01:00"), skipped),
+            Some(at(11, "02:30"))
+        );
+    }
+}
@@ -2,10 +2,16 @@ mod queries;
 mod responses;
 mod types;
 
-pub use queries::{CompletionQuery, EventStreamQuery, EventsQuery, FolderStatusQuery};
+pub use queries::{
+    CompletionQuery, EventStreamQuery, EventsQuery, FolderErrorsQuery, FolderQuery,
+    FolderStatusQuery, PendingDeviceQuery, ScanQuery,
+};
 pub use responses::{EventWaitResult, SyncthingData};
 pub use types::{
-    ConnectionsResponse, DeviceConfig, FolderConfig, RemoteCompletion, SyncthingConfig,
-    SyncthingEvent,
+    BandwidthLimitsPatch, BandwidthOptions, ConnectionsResponse, DeviceConfig, FileVersion,
+    FolderConfig, FolderConfigPatch, FolderDevice, FolderErrorsResponse, FolderFileError,
+    FolderType, GuiAuthPatch, GuiSettings, IgnoresResponse, IgnoresUpdate, PendingDevice,
+    PendingFolder, RemoteCompletion, SyncthingConfig, SyncthingEvent, SystemErrorsResponse,
+    SystemLogLine, SystemLogResponse,
 };
 
@@ -5,6 +5,18 @@ pub struct FolderStatusQuery<'a> {
     pub folder: &'a str,
 }
 
+#[derive(Serialize)]
+pub struct FolderQuery<'a> {
+    pub folder: &'a str,
+}
+
+#[derive(Serialize)]
+pub struct FolderErrorsQuery<'a> {
+    pub folder: &'a str,
+    pub page: u32,
+    pub perpage: u32,
+}
+
 #[derive(Serialize)]
 pub struct EventsQuery {
     pub since: u64,
@@ -14,12 +26,22 @@ pub struct EventsQuery {
 #[derive(Serialize)]
 pub struct EventStreamQuery<'a> {
     pub since: u64,
-    pub limit: u32,
     pub timeout: u64,
     #[serde(rename = "events", skip_serializing_if = "Option::is_none")]
     pub events: Option<&'a [&'a str]>,
 }
 
+#[derive(Serialize)]
+pub struct ScanQuery<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub folder: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+pub struct PendingDeviceQuery<'a> {
+    pub device: &'a str,
+}
+
 #[derive(Serialize)]
 pub struct CompletionQuery<'a> {
     pub device: &'a str,
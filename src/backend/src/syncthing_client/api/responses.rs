@@ -1,15 +1,37 @@
-use crate::syncthing_client::models::{FolderPayload, PeerPayload, SyncthingOverview};
+use crate::syncthing_client::models::{
+    FolderPayload, PeerPayload, PendingDevicePayload, PendingFolderPayload, SyncthingOverview,
+};
+
+use super::types::SyncthingEvent;
 
 /// Aggregated Syncthing data payload consumed by the UI.
 pub struct SyncthingData {
     pub overview: SyncthingOverview,
     pub folders: Vec<FolderPayload>,
     pub peers: Vec<PeerPayload>,
+    pub pending_devices: Vec<PendingDevicePayload>,
+    pub pending_folders: Vec<PendingFolderPayload>,
+}
+
+impl SyncthingData {
+    /// Builds an empty payload carrying only an error message.
+    pub fn error(message: String) -> Self {
+        Self {
+            overview: SyncthingOverview::error(message),
+            folders: Vec::new(),
+            peers: Vec::new(),
+            pending_devices: Vec::new(),
+            pending_folders: Vec::new(),
+        }
+    }
 }
 
 /// Result from long-polling the Syncthing event stream.
 pub struct EventWaitResult {
     pub last_event_id: u64,
     pub has_updates: bool,
+    /// More events happened than Syncthing buffers, so older ones may be lost
+    pub gap: bool,
+    pub events: Vec<SyncthingEvent>,
 }
 
@@ -1,8 +1,8 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct SyncthingConfig {
     #[serde(default)]
     pub folders: Vec<FolderConfig>,
@@ -10,39 +10,166 @@ pub struct SyncthingConfig {
     pub devices: Vec<DeviceConfig>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct FolderConfig {
     pub id: String,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
-    #[serde(default)]
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub folder_type: Option<FolderType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub paused: Option<bool>,
     #[serde(default)]
     pub devices: Vec<FolderDevice>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+/// Syncthing folder type, controlling the direction changes are synced in.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FolderType {
+    SendReceive,
+    SendOnly,
+    ReceiveOnly,
+    ReceiveEncrypted,
+}
+
+/// Partial folder configuration sent via PATCH; unset fields are left untouched.
+#[derive(Debug, Serialize, Default)]
+pub struct FolderConfigPatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub folder_type: Option<FolderType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub devices: Option<Vec<FolderDevice>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct FolderDevice {
     #[serde(rename = "deviceID")]
     pub device_id: String,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct DeviceConfig {
     #[serde(rename = "deviceID")]
     pub device_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paused: Option<bool>,
+    #[serde(
+        default,
+        rename = "maxSendKbps",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_send_kbps: Option<i64>,
+    #[serde(
+        default,
+        rename = "maxRecvKbps",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_recv_kbps: Option<i64>,
+}
+
+/// Global rate limits from `/rest/config/options`, in kbit/s; 0 means unlimited.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BandwidthOptions {
+    #[serde(default)]
+    pub max_send_kbps: i64,
+    #[serde(default)]
+    pub max_recv_kbps: i64,
+}
+
+/// Rate limits sent via PATCH to the options or a device; unset fields are left untouched.
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BandwidthLimitsPatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_send_kbps: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_recv_kbps: Option<u64>,
+}
+
+/// GUI settings as returned by `/rest/config/gui`. The password is a bcrypt hash.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct GuiSettings {
+    #[serde(default)]
+    pub address: String,
+    #[serde(default)]
+    pub user: String,
+    #[serde(default)]
+    pub password: String,
+    #[serde(default, rename = "useTLS")]
+    pub use_tls: bool,
+}
+
+/// GUI credentials sent via PATCH to `/rest/config/gui`. Syncthing hashes a
+/// plain-text password before storing it.
+#[derive(Debug, Serialize)]
+pub struct GuiAuthPatch<'a> {
+    pub user: &'a str,
+    pub password: &'a str,
+    #[serde(rename = "useTLS", skip_serializing_if = "Option::is_none")]
+    pub use_tls: Option<bool>,
+}
+
+/// A device that tried to connect but is not yet part of the configuration,
+/// as returned by `/rest/cluster/pending/devices` (keyed by device ID).
+#[derive(Debug, Deserialize, Clone)]
+pub struct PendingDevice {
+    #[serde(default)]
+    pub time: Option<String>,
     #[serde(default)]
     pub name: Option<String>,
     #[serde(default)]
-    pub paused: Option<bool>,
+    pub address: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Default)]
+/// A folder offered by one or more remote devices,
+/// as returned by `/rest/cluster/pending/folders` (keyed by folder ID).
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct PendingFolder {
+    #[serde(default, rename = "offeredBy")]
+    pub offered_by: BTreeMap<String, PendingFolderOffer>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PendingFolderOffer {
+    #[serde(default)]
+    pub time: Option<String>,
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+impl PendingFolder {
+    /// Returns the first non-empty label any offering device used for this folder.
+    pub fn label(&self) -> Option<&str> {
+        self.offered_by
+            .values()
+            .filter_map(|offer| offer.label.as_deref())
+            .find(|label| !label.trim().is_empty())
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct ConnectionsResponse {
     #[serde(default)]
     pub connections: HashMap<String, ConnectionState>,
+    #[serde(default)]
+    pub total: ConnectionTotals,
+}
+
+/// Traffic counters summed over all connections.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ConnectionTotals {
+    #[serde(default, rename = "inBytesTotal")]
+    pub in_bytes_total: u64,
+    #[serde(default, rename = "outBytesTotal")]
+    pub out_bytes_total: u64,
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -57,6 +184,10 @@ pub struct ConnectionState {
     pub address: Option<String>,
     #[serde(default, rename = "lastSeen")]
     pub last_seen: Option<String>,
+    #[serde(default, rename = "inBytesTotal")]
+    pub in_bytes_total: u64,
+    #[serde(default, rename = "outBytesTotal")]
+    pub out_bytes_total: u64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -68,7 +199,72 @@ pub struct SyncthingEvent {
     pub data: Value,
 }
 
-#[derive(Debug, Deserialize)]
+/// A stored old version of a file, as listed by `/rest/folder/versions`.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FileVersion {
+    pub version_time: String,
+    #[serde(default)]
+    pub mod_time: Option<String>,
+    #[serde(default)]
+    pub size: u64,
+}
+
+/// Ignore patterns of a folder as returned by `/rest/db/ignores`.
+#[derive(Debug, Deserialize, Default)]
+pub struct IgnoresResponse {
+    #[serde(default)]
+    pub ignore: Option<Vec<String>>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Request body for replacing the ignore patterns of a folder.
+#[derive(Debug, Serialize)]
+pub struct IgnoresUpdate<'a> {
+    pub ignore: &'a [String],
+}
+
+/// Response of `/rest/folder/errors`; `errors` is null when there are none.
+#[derive(Debug, Deserialize, Default)]
+pub struct FolderErrorsResponse {
+    #[serde(default)]
+    pub errors: Option<Vec<FolderFileError>>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct FolderFileError {
+    #[serde(default)]
+    pub path: String,
+    #[serde(default)]
+    pub error: String,
+}
+
+/// Response of `/rest/system/error`; `errors` is null when there are none.
+#[derive(Debug, Deserialize, Default)]
+pub struct SystemErrorsResponse {
+    #[serde(default)]
+    pub errors: Option<Vec<SystemLogLine>>,
+}
+
+/// Response of `/rest/system/log`.
+#[derive(Debug, Deserialize, Default)]
+pub struct SystemLogResponse {
+    #[serde(default)]
+    pub messages: Option<Vec<SystemLogLine>>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SystemLogLine {
+    #[serde(default)]
+    pub when: String,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub level: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
 pub struct RemoteCompletion {
     #[allow(dead_code)]
     pub completion: Option<f64>,
@@ -129,6 +325,20 @@ impl SyncthingEvent {
         None
     }
 
+    /// Returns the folder ID if this event marks the end of a folder scan.
+    pub fn finished_scan_folder(&self) -> Option<&str> {
+        if self.event_type != "StateChanged" {
+            return None;
+        }
+        let from = self.data.get("from").and_then(|v| v.as_str())?;
+        let to = self.data.get("to").and_then(|v| v.as_str())?;
+        if from.eq_ignore_ascii_case("scanning") && !to.eq_ignore_ascii_case("scanning") {
+            self.folder_id()
+        } else {
+            None
+        }
+    }
+
     pub fn origin(&self) -> Option<String> {
         self.data
             .get("device")
@@ -1,66 +1,199 @@
-use std::env;
+use std::collections::BTreeMap;
+use std::path::Path;
 use std::time::Duration;
 
-use reqwest::Client;
-use serde_json::Value;
+use chrono::{SecondsFormat, Utc};
+use reqwest::{Certificate, Client};
+use serde_json::{json, Value};
+use tokio::time::timeout;
+use tracing::{debug, warn};
 
 use crate::config::Config;
 use crate::types::MonitorError;
 
-use super::api::{EventStreamQuery, EventWaitResult, SyncthingData, SyncthingEvent};
-use super::core::{DataAggregator, HttpClient};
-use super::helpers::load_api_key;
+use super::api::{
+    BandwidthLimitsPatch, BandwidthOptions, ConnectionsResponse, DeviceConfig, EventStreamQuery,
+    EventWaitResult, FileVersion, FolderConfig, FolderDevice, FolderQuery, FolderType,
+    GuiAuthPatch, GuiSettings, IgnoresResponse, IgnoresUpdate, PendingDeviceQuery, PendingFolder,
+    ScanQuery, SyncthingConfig, SyncthingData, SyncthingEvent, SystemLogResponse,
+};
+use super::core::{
+    preview_changes, resolve_conflict, scan_folder, ConflictResolution, DataAggregator,
+    FolderManager, GuiAddress, GuiConfig, HttpClient, IgnoreMatcher, StatusModel, TrafficTracker,
+};
+use super::helpers::{
+    default_folder_path, encode_path_segment, expand_folder_path, gui_url_from_address,
+    env_override, is_loopback_url, load_gui_certificate, BANDWIDTH_LIMIT_MAX_KBPS,
+    GUI_PASSWORD_MIN_LENGTH, RESCAN_ACK_SECS, SYSTEM_LOG_LIMIT, VERSIONS_LIST_LIMIT,
+};
+use super::models::{
+    BandwidthPayload, ConnectionState, ConnectionStatus, FileVersionsPayload,
+    FolderConflictsPayload, IgnoresPreviewPayload, LogEntryPayload, LogSeverity,
+};
 
 /// High-level client for interacting with the Syncthing REST API.
 #[derive(Clone)]
 pub struct SyncthingClient {
     http: HttpClient,
     http_longpoll: HttpClient,
+    traffic: TrafficTracker,
+    model: StatusModel,
 }
 
 impl SyncthingClient {
-    /// Discovers a Syncthing instance using config/env and prepares an HTTP client.
+    /// Discovers a Syncthing instance from its `config.xml` and prepares an HTTP client.
+    ///
+    /// Connects to the address the GUI actually listens on, over its unix socket
+    /// if it uses one. `SYNCTHING_API_URL` and `SYNCTHING_API_KEY` override the
+    /// discovered values.
     pub async fn discover(config: &Config) -> Result<Self, MonitorError> {
-        let api_key = load_api_key(config).await?;
+        let key_override = env_override("SYNCTHING_API_KEY");
+        let gui = match GuiConfig::load(&config.syncthing_config_xml_path()).await {
+            Ok(gui) => gui,
+            Err(err) if key_override.is_some() => {
+                warn!(error = ?err, "Failed to read Syncthing config.xml, using defaults");
+                GuiConfig::default()
+            }
+            Err(err) => return Err(err),
+        };
+        if !gui.enabled {
+            return Err(MonitorError::Config(
+                "Syncthing's GUI and REST API are disabled in config.xml".to_string(),
+            ));
+        }
+        let api_key = key_override
+            .or_else(|| gui.api_key.clone())
+            .ok_or(MonitorError::MissingApiKey)?;
+
         let mut base_urls = Vec::new();
-        
-        if let Ok(custom) = env::var("SYNCTHING_API_URL") {
-            let trimmed = custom.trim();
-            if !trimmed.is_empty() {
-                push_unique_url(&mut base_urls, trimmed.to_string());
+        let mut unix_socket = None;
+        if let Some(custom) = env_override("SYNCTHING_API_URL") {
+            push_unique_url(&mut base_urls, custom);
+        }
+        match gui.gui_address()? {
+            // The socket never leaves the device, so plain HTTP is fine
+            GuiAddress::Unix(path) if base_urls.is_empty() => {
+                unix_socket = Some(path);
+                base_urls.push("http://localhost".to_string());
+            }
+            GuiAddress::Unix(_) => {}
+            GuiAddress::Tcp { host, port } => {
+                for url in gui.base_urls(&host, port) {
+                    push_unique_url(&mut base_urls, url);
+                }
             }
         }
-        push_unique_url(&mut base_urls, "https://127.0.0.1:8384".to_string());
-        push_unique_url(&mut base_urls, "http://127.0.0.1:8384".to_string());
+
+        // Only send the API key where it cannot be intercepted: over HTTPS to
+        // the holder of Syncthing's own certificate, or over HTTP on loopback
+        let gui_cert = load_gui_certificate(config).await;
+        base_urls.retain(|url| {
+            let trusted = if url.starts_with("https://") {
+                gui_cert.is_some()
+            } else {
+                is_loopback_url(url)
+            };
+            if !trusted {
+                warn!(url = %url, "Skipping Syncthing API URL that cannot be trusted");
+            }
+            trusted
+        });
         if base_urls.is_empty() {
-            base_urls.push("http://127.0.0.1:8384".to_string());
+            return Err(MonitorError::Config(
+                "No trusted Syncthing API URL: HTTPS needs Syncthing's GUI certificate \
+                 and plain HTTP is only used on loopback"
+                    .to_string(),
+            ));
         }
 
         // Standard client for normal API requests (10s timeout)
-        let http_client = Client::builder()
-            .timeout(Duration::from_secs(5))
-            .danger_accept_invalid_certs(true)
-            .build()
-            .map_err(MonitorError::Http)?;
+        let http_client = api_client(
+            Duration::from_secs(5),
+            gui_cert.as_ref(),
+            unix_socket.as_deref(),
+        )?;
 
         // Long-polling client for event stream (60s timeout to support 30s events)
-        let longpoll_client = Client::builder()
-            .timeout(Duration::from_secs(60))
-            .danger_accept_invalid_certs(true)
-            .build()
-            .map_err(MonitorError::Http)?;
+        let longpoll_client = api_client(
+            Duration::from_secs(60),
+            gui_cert.as_ref(),
+            unix_socket.as_deref(),
+        )?;
 
+        let http = HttpClient::new(api_key, http_client, base_urls);
         Ok(Self {
-            http: HttpClient::new(api_key.clone(), http_client, base_urls.clone()),
-            http_longpoll: HttpClient::new(api_key, longpoll_client, base_urls),
+            http_longpoll: http.with_client(longpoll_client),
+            http,
+            traffic: TrafficTracker::default(),
+            model: StatusModel::default(),
         })
     }
 
-    /// Composes the full payload required by the UI.
-    /// Fetches system status, config, recent changes and peer metrics.
+    /// Composes the full payload required by the UI from the cached status model.
+    /// Refetches everything when the model is out of sync, otherwise only polls
+    /// system status and connection counters.
     pub async fn compose_payload(&mut self) -> Result<SyncthingData, MonitorError> {
-        let mut aggregator = DataAggregator::new(&mut self.http);
-        aggregator.compose_payload().await
+        let mut aggregator = DataAggregator::new(&mut self.http, &mut self.traffic);
+        if let Err(err) = aggregator.refresh(&mut self.model).await {
+            self.model.invalidate();
+            return Err(err);
+        }
+        Ok(self.model.compose())
+    }
+
+    /// Updates the cached status model from events received on the event stream.
+    pub fn apply_events(&mut self, events: &[SyncthingEvent]) {
+        self.model.apply_events(events);
+    }
+
+    /// Reports which base URL the API is reached through, and whether it is a fallback.
+    pub fn connection_status(&self) -> ConnectionStatus {
+        let (state, reason) = match self.http.failover_reason() {
+            Some(reason) => (ConnectionState::Degraded, Some(reason)),
+            None => (ConnectionState::Connected, None),
+        };
+        ConnectionStatus {
+            state,
+            reason,
+            base_url: self.http.active_base_url(),
+        }
+    }
+
+    /// Forces a full refetch the next time the payload is composed.
+    pub fn invalidate_model(&mut self) {
+        self.model.invalidate();
+    }
+
+    /// Creates a new shared folder. Returns the path the folder was created at.
+    pub async fn create_folder(
+        &mut self,
+        folder_id: &str,
+        label: Option<&str>,
+        path: Option<&str>,
+        folder_type: Option<FolderType>,
+        device_ids: &[String],
+    ) -> Result<String, MonitorError> {
+        FolderManager::new(&mut self.http)
+            .create(folder_id, label, path, folder_type, device_ids)
+            .await
+    }
+
+    /// Updates label, type and/or shared devices of an existing folder.
+    pub async fn update_folder(
+        &mut self,
+        folder_id: &str,
+        label: Option<&str>,
+        folder_type: Option<FolderType>,
+        device_ids: Option<&[String]>,
+    ) -> Result<(), MonitorError> {
+        FolderManager::new(&mut self.http)
+            .update(folder_id, label, folder_type, device_ids)
+            .await
+    }
+
+    /// Removes a folder from the Syncthing configuration.
+    pub async fn delete_folder(&mut self, folder_id: &str) -> Result<(), MonitorError> {
+        FolderManager::new(&mut self.http).delete(folder_id).await
     }
 
     /// Long-polls the Syncthing event stream for updates.
@@ -70,9 +203,9 @@ impl SyncthingClient {
         timeout: Duration,
     ) -> Result<EventWaitResult, MonitorError> {
         let timeout_secs = timeout.as_secs().clamp(1, 300);
+        // No `limit`: Syncthing would return the newest events and drop older ones
         let query = EventStreamQuery {
             since,
-            limit: 1,
             timeout: timeout_secs,
             events: None,
         };
@@ -91,20 +224,103 @@ impl SyncthingClient {
         Ok(EventWaitResult {
             last_event_id,
             has_updates: !events.is_empty(),
+            gap: has_gap(since, &events),
+            events,
         })
     }
 
-    /// Fetches the GUI address from Syncthing configuration.
-    pub async fn get_gui_address(&mut self) -> Result<String, MonitorError> {
-        let config: Value = self.http.get_json("/rest/config").await?;
-        let address = config
-            .get("gui")
-            .and_then(|gui| gui.get("address"))
-            .and_then(|addr| addr.as_str())
-            .ok_or_else(|| {
-                MonitorError::Syncthing("GUI address not found in config".to_string())
-            })?;
-        Ok(address.to_string())
+    /// Fetches file changes detected on disk since `since` from `/rest/events/disk`.
+    /// Returns immediately with whatever is buffered instead of long-polling.
+    pub async fn disk_changes(&mut self, since: u64) -> Result<EventWaitResult, MonitorError> {
+        let query = EventStreamQuery {
+            since,
+            timeout: 0,
+            events: None,
+        };
+        let events: Vec<SyncthingEvent> = self
+            .http_longpoll
+            .get_json_with_query("/rest/events/disk", &query)
+            .await?;
+
+        let last_event_id = events.iter().map(|event| event.id).max().unwrap_or(since);
+        Ok(EventWaitResult {
+            last_event_id,
+            has_updates: !events.is_empty(),
+            gap: has_gap(since, &events),
+            events,
+        })
+    }
+
+    /// Fetches the most recent lines of Syncthing's log, newest last.
+    pub async fn system_log(&mut self) -> Result<Vec<LogEntryPayload>, MonitorError> {
+        let response: SystemLogResponse = self.http.get_json("/rest/system/log").await?;
+        let messages = response.messages.unwrap_or_default();
+        let skip = messages.len().saturating_sub(SYSTEM_LOG_LIMIT);
+        Ok(messages
+            .into_iter()
+            .skip(skip)
+            .map(|line| LogEntryPayload::from_line(line, LogSeverity::Info))
+            .collect())
+    }
+
+    /// Clears the warnings Syncthing currently reports.
+    pub async fn clear_system_errors(&mut self) -> Result<(), MonitorError> {
+        self.http.post("/rest/system/error/clear").await
+    }
+
+    /// Fetches the bytes sent and received over all connections since Syncthing started.
+    pub async fn transferred_bytes(&mut self) -> Result<u64, MonitorError> {
+        let connections: ConnectionsResponse =
+            self.http.get_json("/rest/system/connections").await?;
+        Ok(connections.total.in_bytes_total + connections.total.out_bytes_total)
+    }
+
+    /// Fetches the local device ID from the system status.
+    pub async fn device_id(&mut self) -> Result<String, MonitorError> {
+        let status: Value = self.http.get_json("/rest/system/status").await?;
+        status
+            .get("myID")
+            .and_then(|id| id.as_str())
+            .map(|id| id.to_string())
+            .ok_or_else(|| MonitorError::Syncthing("Device ID not found in status".to_string()))
+    }
+
+    /// Fetches the GUI settings from Syncthing configuration.
+    pub async fn gui_settings(&mut self) -> Result<GuiSettings, MonitorError> {
+        self.http.get_json("/rest/config/gui").await
+    }
+
+    /// Sets the GUI login, optionally switching the GUI to HTTPS. Syncthing
+    /// hashes the password; it is never stored or returned in plain text.
+    pub async fn set_gui_auth(
+        &mut self,
+        user: &str,
+        password: &str,
+        enable_tls: bool,
+    ) -> Result<(), MonitorError> {
+        let user = user.trim();
+        if user.is_empty() {
+            return Err(MonitorError::Config(
+                "GUI user must not be empty".to_string(),
+            ));
+        }
+        if password.chars().count() < GUI_PASSWORD_MIN_LENGTH {
+            return Err(MonitorError::Config(format!(
+                "GUI password must be at least {GUI_PASSWORD_MIN_LENGTH} characters"
+            )));
+        }
+        let patch = GuiAuthPatch {
+            user,
+            password,
+            use_tls: enable_tls.then_some(true),
+        };
+        self.http.patch_json("/rest/config/gui", &patch).await
+    }
+
+    /// Builds a URL for the GUI if it listens on a specific, reachable address.
+    pub async fn get_gui_url(&mut self) -> Result<Option<String>, MonitorError> {
+        let settings = self.gui_settings().await?;
+        Ok(gui_url_from_address(&settings.address, settings.use_tls))
     }
 
     /// Updates the GUI address in Syncthing configuration.
@@ -124,6 +340,494 @@ impl SyncthingClient {
         self.http.put_json("rest/config", &config).await
     }
 
+    /// Pauses or resumes a single folder via the per-folder config endpoint.
+    pub async fn set_folder_paused(
+        &mut self,
+        folder_id: &str,
+        paused: bool,
+    ) -> Result<(), MonitorError> {
+        let path = format!("/rest/config/folders/{}", encode_path_segment(folder_id));
+        self.http.patch_json(&path, &json!({ "paused": paused })).await
+    }
+
+    /// Pauses or resumes a single device via the per-device config endpoint.
+    pub async fn set_device_paused(
+        &mut self,
+        device_id: &str,
+        paused: bool,
+    ) -> Result<(), MonitorError> {
+        let path = format!("/rest/config/devices/{}", encode_path_segment(device_id));
+        self.http.patch_json(&path, &json!({ "paused": paused })).await
+    }
+
+    /// Fetches the global rate limits and the limits of every peer.
+    pub async fn bandwidth_limits(&mut self) -> Result<BandwidthPayload, MonitorError> {
+        let my_id = self.device_id().await?;
+        let options: BandwidthOptions = self.http.get_json("/rest/config/options").await?;
+        let devices: Vec<DeviceConfig> = self.http.get_json("/rest/config/devices").await?;
+        Ok(BandwidthPayload {
+            global: (&options).into(),
+            devices: devices
+                .iter()
+                .filter(|device| device.device_id != my_id)
+                .map(Into::into)
+                .collect(),
+        })
+    }
+
+    /// Sets rate limits in kbit/s for a peer, or the global limits when `device_id` is None.
+    /// Limits left as None are not changed; 0 removes a limit.
+    pub async fn set_bandwidth_limits(
+        &mut self,
+        device_id: Option<&str>,
+        max_send_kbps: Option<u64>,
+        max_recv_kbps: Option<u64>,
+    ) -> Result<(), MonitorError> {
+        if max_send_kbps.is_none() && max_recv_kbps.is_none() {
+            return Err(MonitorError::Config("No bandwidth limit given".to_string()));
+        }
+        if let Some(limit) = [max_send_kbps, max_recv_kbps]
+            .into_iter()
+            .flatten()
+            .find(|limit| *limit > BANDWIDTH_LIMIT_MAX_KBPS)
+        {
+            return Err(MonitorError::Config(format!(
+                "Bandwidth limit {limit} kbit/s exceeds the maximum of {BANDWIDTH_LIMIT_MAX_KBPS} kbit/s"
+            )));
+        }
+
+        let patch = BandwidthLimitsPatch {
+            max_send_kbps,
+            max_recv_kbps,
+        };
+        match device_id {
+            Some(device_id) => {
+                let config: SyncthingConfig = self.http.get_json("/rest/config").await?;
+                if !config
+                    .devices
+                    .iter()
+                    .any(|device| device.device_id == device_id)
+                {
+                    return Err(MonitorError::Config(format!(
+                        "Device {device_id} is not configured"
+                    )));
+                }
+                let path = format!("/rest/config/devices/{}", encode_path_segment(device_id));
+                self.http.patch_json(&path, &patch).await
+            }
+            None => self.http.patch_json("/rest/config/options", &patch).await,
+        }
+    }
+
+    /// Pauses or resumes all configured devices at once.
+    pub async fn set_all_devices_paused(&mut self, paused: bool) -> Result<(), MonitorError> {
+        if paused {
+            self.http.post("/rest/system/pause").await
+        } else {
+            self.http.post("/rest/system/resume").await
+        }
+    }
+
+    /// Triggers an immediate rescan of one folder, or of all folders when `folder` is None.
+    ///
+    /// Syncthing only answers once the scan has finished, so this waits just long
+    /// enough to catch a rejected request and leaves the scan running in the
+    /// background. Completion shows up as the folder's state events.
+    pub async fn rescan(&mut self, folder: Option<&str>) -> Result<(), MonitorError> {
+        let mut http = self.http_longpoll.clone();
+        let folder = folder.map(str::to_string);
+        let mut scan = tokio::spawn(async move {
+            let query = ScanQuery {
+                folder: folder.as_deref(),
+            };
+            let result = http.post_with_query("/rest/db/scan", &query).await;
+            if let Err(err) = &result {
+                debug!(folder = ?folder, error = ?err, "Rescan request ended with an error");
+            }
+            result
+        });
+        match timeout(Duration::from_secs(RESCAN_ACK_SECS), &mut scan).await {
+            Ok(joined) => joined.map_err(|err| MonitorError::Syncthing(err.to_string()))?,
+            // Still scanning
+            Err(_) => Ok(()),
+        }
+    }
+
+    /// Lists the IDs of all folders that are not paused.
+    pub async fn active_folder_ids(&mut self) -> Result<Vec<String>, MonitorError> {
+        let config: SyncthingConfig = self.http.get_json("/rest/config").await?;
+        Ok(config
+            .folders
+            .into_iter()
+            .filter(|folder| !folder.paused.unwrap_or(false))
+            .map(|folder| folder.id)
+            .collect())
+    }
+
+    /// Accepts a pending device by adding it to the configuration under the given name.
+    pub async fn accept_pending_device(
+        &mut self,
+        device_id: &str,
+        name: &str,
+    ) -> Result<(), MonitorError> {
+        let device = DeviceConfig {
+            device_id: device_id.to_string(),
+            name: Some(name.to_string()),
+            paused: None,
+            max_send_kbps: None,
+            max_recv_kbps: None,
+        };
+        self.http.post_json("/rest/config/devices", &device).await
+    }
+
+    /// Dismisses a pending device so it no longer shows up until it connects again.
+    pub async fn dismiss_pending_device(&mut self, device_id: &str) -> Result<(), MonitorError> {
+        let query = PendingDeviceQuery { device: device_id };
+        self.http
+            .delete_with_query("/rest/cluster/pending/devices", &query)
+            .await
+    }
+
+    /// Accepts a pending folder share, sharing it back with every device that offered it.
+    /// Uses a path under the default folder root when `path` is None. A folder
+    /// that already exists keeps its path and existing shares, and only gains
+    /// the offering devices. Returns the folder's path.
+    pub async fn accept_pending_folder(
+        &mut self,
+        folder_id: &str,
+        path: Option<&str>,
+    ) -> Result<String, MonitorError> {
+        let pending = self.find_pending_folder(folder_id).await?;
+        let path = path.map(str::trim).filter(|path| !path.is_empty());
+
+        let config: SyncthingConfig = self.http.get_json("/rest/config").await?;
+        if let Some(existing) = config.folders.iter().find(|folder| folder.id == folder_id) {
+            if let Some(path) = path {
+                return Err(MonitorError::Config(format!(
+                    "Folder {folder_id} already exists, it cannot be moved to {path}"
+                )));
+            }
+            let existing_path = existing.path.clone().unwrap_or_default();
+            self.share_folder_with(folder_id, pending.offered_by.keys())
+                .await?;
+            return Ok(existing_path);
+        }
+
+        let label = pending.label().unwrap_or(folder_id).to_string();
+        let path = match path {
+            Some(path) if path.starts_with('/') => path.to_string(),
+            Some(path) => {
+                return Err(MonitorError::Config(format!(
+                    "Folder path must be absolute: {path}"
+                )))
+            }
+            None => default_folder_path(&label),
+        };
+
+        let folder = FolderConfig {
+            id: folder_id.to_string(),
+            label: Some(label),
+            path: Some(path.clone()),
+            folder_type: None,
+            paused: None,
+            devices: pending
+                .offered_by
+                .keys()
+                .map(|device_id| FolderDevice {
+                    device_id: device_id.clone(),
+                })
+                .collect(),
+        };
+        self.http.post_json("/rest/config/folders", &folder).await?;
+        Ok(path)
+    }
+
+    /// Adds devices to the device list of an existing folder, leaving the rest
+    /// of its configuration untouched.
+    async fn share_folder_with<'a>(
+        &mut self,
+        folder_id: &str,
+        device_ids: impl Iterator<Item = &'a String>,
+    ) -> Result<(), MonitorError> {
+        let path = format!("/rest/config/folders/{}", encode_path_segment(folder_id));
+        let mut folder: Value = self.http.get_json(&path).await?;
+        let devices = folder
+            .get_mut("devices")
+            .and_then(Value::as_array_mut)
+            .ok_or_else(|| {
+                MonitorError::Syncthing(format!("Folder {folder_id} has no device list"))
+            })?;
+        for device_id in device_ids {
+            let shared = devices
+                .iter()
+                .any(|device| device.get("deviceID").and_then(Value::as_str) == Some(device_id));
+            if !shared {
+                devices.push(json!({ "deviceID": device_id }));
+            }
+        }
+        self.http.put_json(&path, &folder).await
+    }
+
+    /// Ignores a pending folder share by adding it to the ignored folders
+    /// of every device that offered it, so it is not offered again.
+    pub async fn ignore_pending_folder(&mut self, folder_id: &str) -> Result<(), MonitorError> {
+        let pending = self.find_pending_folder(folder_id).await?;
+        let now = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+
+        for (device_id, offer) in &pending.offered_by {
+            let path = format!("/rest/config/devices/{}", encode_path_segment(device_id));
+            let mut device: Value = self.http.get_json(&path).await?;
+            let entry = json!({
+                "id": folder_id,
+                "label": offer.label.clone().unwrap_or_default(),
+                "time": now,
+            });
+
+            if let Some(device_obj) = device.as_object_mut() {
+                let ignored = device_obj
+                    .entry("ignoredFolders")
+                    .or_insert_with(|| Value::Array(Vec::new()));
+                if !ignored.is_array() {
+                    *ignored = Value::Array(Vec::new());
+                }
+                if let Some(list) = ignored.as_array_mut() {
+                    let already_ignored = list
+                        .iter()
+                        .any(|item| item.get("id").and_then(|v| v.as_str()) == Some(folder_id));
+                    if !already_ignored {
+                        list.push(entry);
+                    }
+                }
+            }
+
+            self.http.put_json(&path, &device).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Looks up a single pending folder offer by ID.
+    async fn find_pending_folder(
+        &mut self,
+        folder_id: &str,
+    ) -> Result<PendingFolder, MonitorError> {
+        let mut pending: BTreeMap<String, PendingFolder> =
+            self.http.get_json("/rest/cluster/pending/folders").await?;
+        pending.remove(folder_id).ok_or_else(|| {
+            MonitorError::Syncthing(format!("No pending offer for folder {folder_id}"))
+        })
+    }
+
+    /// Scans folders on disk for sync-conflict copies.
+    /// Scans every configured folder when `folder_id` is None.
+    pub async fn scan_conflicts(
+        &mut self,
+        folder_id: Option<&str>,
+    ) -> Result<Vec<FolderConflictsPayload>, MonitorError> {
+        let config: SyncthingConfig = self.http.get_json("/rest/config").await?;
+        let mut results = Vec::new();
+
+        for folder in config.folders {
+            if folder_id.is_some_and(|id| id != folder.id) {
+                continue;
+            }
+            let label = folder.label.clone().unwrap_or_else(|| folder.id.clone());
+            let Some(path) = folder.path.as_deref() else {
+                continue;
+            };
+            let root = expand_folder_path(path);
+            let scan = tokio::task::spawn_blocking(move || scan_folder(&root))
+                .await
+                .map_err(|err| MonitorError::Io(std::io::Error::other(err)))?;
+
+            results.push(match scan {
+                Ok((conflicts, truncated)) => FolderConflictsPayload {
+                    folder_id: folder.id,
+                    label,
+                    conflicts,
+                    truncated,
+                    error: None,
+                },
+                Err(err) => FolderConflictsPayload {
+                    folder_id: folder.id,
+                    label,
+                    conflicts: Vec::new(),
+                    truncated: false,
+                    error: Some(err.to_string()),
+                },
+            });
+        }
+
+        if let Some(folder_id) = folder_id {
+            if results.is_empty() {
+                return Err(MonitorError::Config(format!(
+                    "Folder {folder_id} does not exist"
+                )));
+            }
+        }
+        Ok(results)
+    }
+
+    /// Resolves a conflict copy in a folder; `path` is relative to the folder root.
+    /// Returns a short description of what was done.
+    pub async fn resolve_conflict(
+        &mut self,
+        folder_id: &str,
+        path: &str,
+        resolution: ConflictResolution,
+    ) -> Result<String, MonitorError> {
+        let config: SyncthingConfig = self.http.get_json("/rest/config").await?;
+        let root = config
+            .folders
+            .iter()
+            .find(|folder| folder.id == folder_id)
+            .and_then(|folder| folder.path.as_deref())
+            .map(expand_folder_path)
+            .ok_or_else(|| MonitorError::Config(format!("Folder {folder_id} does not exist")))?;
+
+        let path = path.to_string();
+        tokio::task::spawn_blocking(move || resolve_conflict(&root, &path, resolution))
+            .await
+            .map_err(|err| MonitorError::Io(std::io::Error::other(err)))?
+    }
+
+    /// Lists files with stored versions in a folder, optionally limited to paths under `prefix`.
+    /// Returns the files (bounded) and whether the list was truncated.
+    pub async fn list_versions(
+        &mut self,
+        folder_id: &str,
+        prefix: Option<&str>,
+    ) -> Result<(Vec<FileVersionsPayload>, bool), MonitorError> {
+        let query = FolderQuery { folder: folder_id };
+        let versions: BTreeMap<String, Vec<FileVersion>> = self
+            .http
+            .get_json_with_query("/rest/folder/versions", &query)
+            .await?;
+
+        let prefix = prefix.map(|prefix| prefix.trim_start_matches('/'));
+        let mut files = versions.into_iter().filter(|(path, _)| match prefix {
+            Some(prefix) => path.starts_with(prefix),
+            None => true,
+        });
+        let listed: Vec<FileVersionsPayload> = files
+            .by_ref()
+            .take(VERSIONS_LIST_LIMIT)
+            .map(|(path, versions)| FileVersionsPayload::from_parts(path, versions))
+            .collect();
+        let truncated = files.next().is_some();
+        Ok((listed, truncated))
+    }
+
+    /// Restores a stored version of a file, replacing the current copy.
+    /// `version_time` must match one of the versions listed for `path`.
+    pub async fn restore_version(
+        &mut self,
+        folder_id: &str,
+        path: &str,
+        version_time: &str,
+    ) -> Result<(), MonitorError> {
+        let query = FolderQuery { folder: folder_id };
+        let body = BTreeMap::from([(path, version_time)]);
+        let failures: BTreeMap<String, String> = self
+            .http
+            .post_json_with_query("/rest/folder/versions", &query, &body)
+            .await?;
+
+        match failures.into_iter().next() {
+            Some((path, error)) => Err(MonitorError::Syncthing(format!(
+                "Failed to restore {path}: {error}"
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the ignore patterns of a folder as written in its `.stignore`.
+    pub async fn get_ignores(&mut self, folder_id: &str) -> Result<Vec<String>, MonitorError> {
+        let query = FolderQuery { folder: folder_id };
+        let response: IgnoresResponse = self
+            .http
+            .get_json_with_query("/rest/db/ignores", &query)
+            .await?;
+        if let Some(error) = response.error.filter(|error| !error.is_empty()) {
+            return Err(MonitorError::Syncthing(error));
+        }
+        Ok(response.ignore.unwrap_or_default())
+    }
+
+    /// Validates proposed ignore patterns and lists the files on disk whose
+    /// ignored state would change compared to the current patterns. A folder
+    /// that cannot be walked, e.g. because it does not exist yet, yields an
+    /// empty preview with a warning.
+    pub async fn preview_ignores(
+        &mut self,
+        folder_id: &str,
+        patterns: &[String],
+    ) -> Result<IgnoresPreviewPayload, MonitorError> {
+        let (proposed, mut warnings) = IgnoreMatcher::parse(patterns)
+            .map_err(|errors| MonitorError::Config(errors.join("; ")))?;
+        let current = self.get_ignores(folder_id).await?;
+        // Unparseable current patterns are compared as ignoring nothing,
+        // which the preview has to say
+        let current = match IgnoreMatcher::parse(&current) {
+            Ok((current, _)) => current,
+            Err(errors) => {
+                warnings.push(format!(
+                    "The current patterns cannot be evaluated ({}); the preview treats them as ignoring nothing",
+                    errors.join("; ")
+                ));
+                IgnoreMatcher::default()
+            }
+        };
+
+        let config: SyncthingConfig = self.http.get_json("/rest/config").await?;
+        let root = config
+            .folders
+            .iter()
+            .find(|folder| folder.id == folder_id)
+            .and_then(|folder| folder.path.as_deref())
+            .map(expand_folder_path)
+            .ok_or_else(|| MonitorError::Config(format!("Folder {folder_id} does not exist")))?;
+
+        let walk_root = root.clone();
+        let walked =
+            tokio::task::spawn_blocking(move || preview_changes(&walk_root, &current, &proposed))
+                .await
+                .map_err(|err| MonitorError::Io(std::io::Error::other(err)))?;
+        let mut preview = match walked {
+            Ok(preview) => preview,
+            Err(err) => {
+                warn!(path = %root.display(), error = ?err, "Failed to walk folder for ignore preview");
+                IgnoresPreviewPayload {
+                    warnings: vec![format!(
+                        "No preview: {} could not be read ({err})",
+                        root.display()
+                    )],
+                    ..IgnoresPreviewPayload::default()
+                }
+            }
+        };
+        preview.warnings.extend(warnings);
+        Ok(preview)
+    }
+
+    /// Replaces the ignore patterns of a folder. Syncthing rescans the folder afterwards.
+    pub async fn set_ignores(
+        &mut self,
+        folder_id: &str,
+        patterns: &[String],
+    ) -> Result<(), MonitorError> {
+        let query = FolderQuery { folder: folder_id };
+        let body = IgnoresUpdate { ignore: patterns };
+        let response: IgnoresResponse = self
+            .http
+            .post_json_with_query("/rest/db/ignores", &query, &body)
+            .await?;
+        match response.error.filter(|error| !error.is_empty()) {
+            Some(error) => Err(MonitorError::Syncthing(error)),
+            None => Ok(()),
+        }
+    }
+
     /// Restarts Syncthing via the API.
     /// Sends a POST request to /rest/system/restart which will cause Syncthing to restart itself.
     pub async fn restart(&mut self) -> Result<(), MonitorError> {
@@ -131,9 +835,41 @@ impl SyncthingClient {
     }
 }
 
+/// Builds an HTTP client that trusts Syncthing's GUI certificate as its only
+/// root. The hostname is not checked since the certificate names the device,
+/// not the address it is reached on; the pinned key is what matters.
+fn api_client(
+    timeout: Duration,
+    gui_cert: Option<&Certificate>,
+    unix_socket: Option<&Path>,
+) -> Result<Client, MonitorError> {
+    let mut builder = Client::builder()
+        .timeout(timeout)
+        .tls_built_in_root_certs(false)
+        .danger_accept_invalid_hostnames(true);
+    if let Some(cert) = gui_cert {
+        builder = builder.add_root_certificate(cert.clone());
+    }
+    if let Some(path) = unix_socket {
+        builder = builder.unix_socket(path);
+    }
+    builder.build().map_err(MonitorError::Http)
+}
+
 /// Adds a URL to the list only if it's not already present.
 fn push_unique_url(list: &mut Vec<String>, candidate: String) {
     if !list.iter().any(|existing| existing == &candidate) {
         list.push(candidate);
     }
 }
+
+/// Whether events after `since` are missing from the batch. Event IDs are
+/// consecutive per subscription, so a jump means Syncthing's buffer overflowed.
+fn has_gap(since: u64, events: &[SyncthingEvent]) -> bool {
+    since > 0
+        && events
+            .iter()
+            .map(|event| event.id)
+            .min()
+            .is_some_and(|first| first > since + 1)
+}
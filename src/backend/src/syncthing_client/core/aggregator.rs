@@ -1,98 +1,178 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::BTreeMap;
+use std::future::Future;
 
 use serde_json::Value;
+use tokio::task::JoinSet;
 use tracing::warn;
 
 use crate::types::MonitorError;
 
 use super::super::api::{
-    CompletionQuery, ConnectionsResponse, DeviceConfig, EventsQuery, FolderConfig,
-    FolderStatusQuery, RemoteCompletion, SyncthingConfig, SyncthingData, SyncthingEvent,
+    CompletionQuery, ConnectionsResponse, DeviceConfig, EventsQuery, FolderErrorsQuery,
+    FolderErrorsResponse, FolderStatusQuery, PendingDevice, PendingFolder, RemoteCompletion,
+    SyncthingConfig, SyncthingEvent, SystemErrorsResponse,
+};
+use super::super::helpers::{
+    is_file_event, FOLDER_ERRORS_LIMIT, MAX_CONCURRENT_REQUESTS, RECENT_EVENTS_LIMIT,
 };
-use super::super::helpers::{format_relative_time, is_file_event, RECENT_EVENTS_LIMIT};
 use super::super::models::{
-    FolderChange, FolderPayload, FolderPeerNeedSummary, PeerPayload, PeerProgress,
-    SyncthingOverview,
+    FolderItemError, LogEntryPayload, LogSeverity, PendingDevicePayload, PendingFolderPayload,
 };
 use super::http::HttpClient;
+use super::model::StatusModel;
+use super::traffic::TrafficTracker;
 
-/// Aggregates data from multiple Syncthing API endpoints into UI-ready payloads.
+/// Fetches data from multiple Syncthing API endpoints into the cached status model.
 pub struct DataAggregator<'a> {
     http: &'a mut HttpClient,
+    traffic: &'a mut TrafficTracker,
+}
+
+/// Status of a single folder plus its failed items when it reports any.
+struct FolderFetch {
+    status: Value,
+    errors: Option<Vec<FolderItemError>>,
 }
 
 impl<'a> DataAggregator<'a> {
-    pub fn new(http: &'a mut HttpClient) -> Self {
-        Self { http }
+    pub fn new(http: &'a mut HttpClient, traffic: &'a mut TrafficTracker) -> Self {
+        Self { http, traffic }
     }
 
-    /// Composes the full payload required by the UI.
-    /// Fetches system status, config, recent changes and peer metrics.
-    pub async fn compose_payload(&mut self) -> Result<SyncthingData, MonitorError> {
-        let status_value: Value = self.http.get_json("/rest/system/status").await?;
-        let config: SyncthingConfig = self.http.get_json("/rest/config").await?;
-        let folder_ids: HashSet<String> = config.folders.iter().map(|f| f.id.clone()).collect();
-        let latest_changes = self.latest_folder_changes(&folder_ids).await?;
-        let mut folders = Vec::new();
+    /// Brings the model up to date: a full refetch when it is out of sync,
+    /// otherwise only system status, connection counters and changed pending lists.
+    pub async fn refresh(&mut self, model: &mut StatusModel) -> Result<(), MonitorError> {
+        if !model.is_synced() {
+            return self.refresh_all(model).await;
+        }
 
-        let connections = match self.fetch_connections().await {
+        self.refresh_system(model).await?;
+        if model.pending_dirty() {
+            self.refresh_pending(model).await;
+        }
+        Ok(())
+    }
+
+    /// Refetches system status, config, recent changes and peer metrics.
+    /// Folder status and remote completion are fetched concurrently; a failure
+    /// only affects the folder or peer it belongs to.
+    async fn refresh_all(&mut self, model: &mut StatusModel) -> Result<(), MonitorError> {
+        model.reset();
+        // Read the event ID first, so anything that changes while the rest is
+        // fetched arrives as an event and is not taken as already seen
+        let last_event_id = self.load_recent_changes(model).await?;
+
+        let mut http = self.http.clone();
+        let (system, config) = tokio::join!(
+            self.refresh_system(model),
+            http.get_json::<SyncthingConfig>("/rest/config"),
+        );
+        system?;
+        model.config = config?;
+        model.prune_completions();
+        self.refresh_pending(model).await;
+
+        let folder_ids: Vec<String> = model
+            .config
+            .folders
+            .iter()
+            .map(|folder| folder.id.clone())
+            .collect();
+        for (folder_id, result) in run_bounded(self.http, folder_ids, fetch_folder).await {
+            match result {
+                Ok(fetch) => {
+                    if let Some(errors) = fetch.errors {
+                        model.folder_errors.insert(folder_id.clone(), errors);
+                    }
+                    model.folder_status.insert(folder_id, fetch.status);
+                }
+                Err(err) => {
+                    warn!(folder = %folder_id, error = ?err, "Failed to fetch folder status");
+                    model.mark_folder_unavailable(folder_id, err.to_string());
+                }
+            }
+        }
+
+        let pairs = self.stale_completion_pairs(model);
+        for (pair, result) in run_bounded(self.http, pairs, query_remote_completion).await {
+            match result {
+                Ok(remote_completion) => model.store_completion(pair, remote_completion),
+                Err(err) => {
+                    // Keeps the previously cached value for this peer, if any
+                    warn!(
+                        folder = %pair.0,
+                        device = %pair.1,
+                        error = ?err,
+                        "Failed to query remote completion"
+                    );
+                }
+            }
+        }
+
+        model.mark_synced(last_event_id);
+        Ok(())
+    }
+
+    /// Fetches system status, connections, warnings and the discovery cache concurrently.
+    /// Only a failure to fetch the system status is an error.
+    async fn refresh_system(&mut self, model: &mut StatusModel) -> Result<(), MonitorError> {
+        let mut connections_http = self.http.clone();
+        let mut warnings_http = self.http.clone();
+        let mut discovery_http = self.http.clone();
+        let (status, connections, warnings, discovery) = tokio::join!(
+            self.http.get_json::<Value>("/rest/system/status"),
+            connections_http.get_json::<ConnectionsResponse>("/rest/system/connections"),
+            warnings_http.get_json::<SystemErrorsResponse>("/rest/system/error"),
+            discovery_http.get_json::<BTreeMap<String, Value>>("/rest/system/discovery"),
+        );
+        model.status = status?;
+        self.apply_connections(model, connections);
+        apply_system_warnings(model, warnings);
+        match discovery {
+            Ok(discovery) => model.discovery = discovery,
+            Err(err) => warn!(error = ?err, "Failed to fetch discovery cache"),
+        }
+        Ok(())
+    }
+
+    /// Stores connection state and derives transfer rates from its counters.
+    fn apply_connections(
+        &mut self,
+        model: &mut StatusModel,
+        connections: Result<ConnectionsResponse, MonitorError>,
+    ) {
+        model.connections = match connections {
             Ok(data) => data,
             Err(err) => {
                 warn!(error = ?err, "Failed to fetch peer connections");
                 ConnectionsResponse::default()
             }
         };
+        model.traffic = self.traffic.update(&model.connections);
+    }
 
-        let overview = SyncthingOverview::from_value(&status_value);
-        let my_id = overview.my_id.clone();
-
-        let (folder_peer_summaries, peer_progress) = self
-            .collect_peer_metrics(&config.folders, my_id.as_deref())
-            .await;
-
-        for folder in &config.folders {
-            let query = FolderStatusQuery {
-                folder: folder.id.as_str(),
-            };
-            let status: Value = self.http.get_json_with_query("/rest/db/status", &query).await?;
-            // Keep UI contract: a Vec, but only ever include the latest (0..1)
-            let last_changes = latest_changes
-                .get(&folder.id)
-                .cloned()
-                .into_iter()
-                .collect::<Vec<_>>();
-            let peer_need_summary = folder_peer_summaries.get(&folder.id).copied();
-            folders.push(FolderPayload::from_parts(
-                folder,
-                &status,
-                last_changes,
-                peer_need_summary,
-            ));
-        }
-
-        let peers = self.compose_peers(
-            &config.devices,
-            my_id.as_deref(),
-            &peer_progress,
-            &connections,
-        );
+    async fn refresh_pending(&mut self, model: &mut StatusModel) {
+        let pending_devices = match self.fetch_pending_devices().await {
+            Ok(data) => data,
+            Err(err) => {
+                warn!(error = ?err, "Failed to fetch pending devices");
+                Vec::new()
+            }
+        };
 
-        Ok(SyncthingData {
-            overview,
-            folders,
-            peers,
-        })
+        let pending_folders = match self.fetch_pending_folders(&model.config.devices).await {
+            Ok(data) => data,
+            Err(err) => {
+                warn!(error = ?err, "Failed to fetch pending folders");
+                Vec::new()
+            }
+        };
+        model.set_pending(pending_devices, pending_folders);
     }
 
-    /// Collects the latest changed file per folder from recent events.
-    async fn latest_folder_changes(
-        &mut self,
-        allowed: &HashSet<String>,
-    ) -> Result<HashMap<String, FolderChange>, MonitorError> {
-        if allowed.is_empty() {
-            return Ok(HashMap::new());
-        }
-
+    /// Records the latest changed file per folder from recent events.
+    /// Returns the newest event ID seen, from which the event stream continues.
+    async fn load_recent_changes(&mut self, model: &mut StatusModel) -> Result<u64, MonitorError> {
         let query = EventsQuery {
             since: 0,
             limit: RECENT_EVENTS_LIMIT,
@@ -101,56 +181,22 @@ impl<'a> DataAggregator<'a> {
             .http
             .get_json_with_query("/rest/events", &query)
             .await?;
-        events.sort_by(|a, b| b.id.cmp(&a.id));
+        events.sort_by(|a, b| a.id.cmp(&b.id));
 
-        let mut changes: HashMap<String, FolderChange> = HashMap::new();
-        for event in events {
-            if !is_file_event(&event.event_type) {
-                continue;
-            }
-            let Some(folder_id) = event.folder_id() else {
-                continue;
-            };
-            if !allowed.contains(folder_id) {
-                continue;
-            }
-            // If we already recorded the latest change for this folder, skip
-            if changes.contains_key(folder_id) {
-                continue;
-            }
-            if let Some(file_name) = event.file_name() {
-                changes.insert(
-                    folder_id.to_string(),
-                    FolderChange {
-                        name: file_name,
-                        action: event.action().unwrap_or_else(|| event.event_type.clone()),
-                        when: format_relative_time(&event.time),
-                        origin: event.origin(),
-                    },
-                );
+        for event in &events {
+            if is_file_event(&event.event_type) {
+                model.record_change(event);
             }
         }
 
-        Ok(changes)
+        Ok(events.last().map(|event| event.id).unwrap_or(0))
     }
 
-    /// Collects sync progress metrics for all peers across all folders.
-    async fn collect_peer_metrics(
-        &mut self,
-        folders: &[FolderConfig],
-        my_id: Option<&str>,
-    ) -> (
-        HashMap<String, FolderPeerNeedSummary>,
-        HashMap<String, PeerProgress>,
-    ) {
-        let mut folder_summaries: HashMap<String, FolderPeerNeedSummary> = HashMap::new();
-        let mut peer_progress: HashMap<String, PeerProgress> = HashMap::new();
-
-        for folder in folders {
-            if folder.devices.is_empty() {
-                continue;
-            }
-
+    /// Folder/peer pairs whose remote completion is missing or too old to reuse.
+    fn stale_completion_pairs(&self, model: &StatusModel) -> Vec<(String, String)> {
+        let my_id = model.status.get("myID").and_then(|v| v.as_str());
+        let mut pairs = Vec::new();
+        for folder in &model.config.folders {
             for device in &folder.devices {
                 if device.device_id.is_empty() {
                     continue;
@@ -161,105 +207,164 @@ impl<'a> DataAggregator<'a> {
                 {
                     continue;
                 }
-
-                match self
-                    .query_remote_completion(folder.id.as_str(), device.device_id.as_str())
-                    .await
-                {
-                    Ok(remote_completion) => {
-                        let need = remote_completion.need_bytes.unwrap_or(0);
-                        if need > 0 {
-                            let entry = folder_summaries
-                                .entry(folder.id.clone())
-                                .or_insert_with(FolderPeerNeedSummary::default);
-                            entry.peer_count = entry.peer_count.saturating_add(1);
-                            entry.need_bytes = entry.need_bytes.saturating_add(need);
-                        }
-
-                        peer_progress
-                            .entry(device.device_id.clone())
-                            .or_insert_with(PeerProgress::default)
-                            .record(folder, &remote_completion);
-                    }
-                    Err(err) => {
-                        warn!(
-                            folder = %folder.id,
-                            device = %device.device_id,
-                            error = ?err,
-                            "Failed to query remote completion"
-                        );
-                    }
+                let pair = (folder.id.clone(), device.device_id.clone());
+                if !model.completion_is_fresh(&pair) {
+                    pairs.push(pair);
                 }
             }
         }
+        pairs
+    }
 
-        (folder_summaries, peer_progress)
+    /// Fetches devices that attempted to connect but are not configured yet.
+    async fn fetch_pending_devices(&mut self) -> Result<Vec<PendingDevicePayload>, MonitorError> {
+        let pending: BTreeMap<String, PendingDevice> =
+            self.http.get_json("/rest/cluster/pending/devices").await?;
+        Ok(pending
+            .iter()
+            .map(|(device_id, device)| PendingDevicePayload::from_parts(device_id, device))
+            .collect())
     }
 
-    /// Builds peer payloads from device configuration and collected metrics.
-    fn compose_peers(
-        &self,
+    /// Fetches folders offered by remote devices that are not shared with us yet.
+    async fn fetch_pending_folders(
+        &mut self,
         devices: &[DeviceConfig],
-        my_id: Option<&str>,
-        peer_progress: &HashMap<String, PeerProgress>,
-        connections: &ConnectionsResponse,
-    ) -> Vec<PeerPayload> {
-        let mut peers = Vec::new();
-        for device in devices {
-            if device.device_id.is_empty() {
-                continue;
-            }
-            if my_id
-                .map(|local| local == device.device_id.as_str())
-                .unwrap_or(false)
-            {
-                continue;
-            }
+    ) -> Result<Vec<PendingFolderPayload>, MonitorError> {
+        let pending: BTreeMap<String, PendingFolder> =
+            self.http.get_json("/rest/cluster/pending/folders").await?;
+        Ok(pending
+            .iter()
+            .map(|(folder_id, folder)| PendingFolderPayload::from_parts(folder_id, folder, devices))
+            .collect())
+    }
+}
 
-            let connection = connections.connections.get(&device.device_id);
-            let progress = peer_progress.get(&device.device_id);
-            let paused =
-                device.paused.unwrap_or(false) || connection.map(|c| c.paused).unwrap_or(false);
-
-            peers.push(PeerPayload {
-                id: device.device_id.clone(),
-                name: device
-                    .name
-                    .clone()
-                    .unwrap_or_else(|| device.device_id.clone()),
-                connected: connection.map(|c| c.connected).unwrap_or(false),
-                paused,
-                address: connection.and_then(|c| c.address.clone()),
-                client_version: connection.and_then(|c| c.client_version.clone()),
-                last_seen: connection.and_then(|c| c.last_seen.clone()),
-                completion: progress.and_then(|p| p.avg_completion()),
-                need_bytes: progress.and_then(|p| p.outstanding_need()),
-                folders: progress.map(|p| p.folders.clone()).unwrap_or_default(),
-            });
+/// Stores the warnings Syncthing currently reports, leaving the model untouched
+/// if they could not be fetched.
+fn apply_system_warnings(
+    model: &mut StatusModel,
+    warnings: Result<SystemErrorsResponse, MonitorError>,
+) {
+    match warnings {
+        Ok(response) => {
+            model.system_warnings = response
+                .errors
+                .unwrap_or_default()
+                .into_iter()
+                .map(|line| LogEntryPayload::from_line(line, LogSeverity::Warning))
+                .collect();
         }
-
-        peers.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-        peers
+        Err(err) => warn!(error = ?err, "Failed to fetch Syncthing warnings"),
     }
+}
 
-    /// Queries the remote completion status for a specific folder and device.
-    async fn query_remote_completion(
-        &mut self,
-        folder_id: &str,
-        device_id: &str,
-    ) -> Result<RemoteCompletion, MonitorError> {
-        let query = CompletionQuery {
-            folder: folder_id,
-            device: device_id,
-        };
-        self.http
-            .get_json_with_query("/rest/db/completion", &query)
-            .await
+/// Runs one request per key with at most `MAX_CONCURRENT_REQUESTS` in flight.
+/// Each request gets its own handle on the shared HTTP client.
+async fn run_bounded<K, T, F, Fut>(
+    http: &HttpClient,
+    keys: Vec<K>,
+    request: F,
+) -> Vec<(K, Result<T, MonitorError>)>
+where
+    K: Clone + Send + 'static,
+    T: Send + 'static,
+    F: Fn(HttpClient, K) -> Fut,
+    Fut: Future<Output = Result<T, MonitorError>> + Send + 'static,
+{
+    let mut tasks = JoinSet::new();
+    let mut results = Vec::with_capacity(keys.len());
+
+    for key in keys {
+        if tasks.len() >= MAX_CONCURRENT_REQUESTS {
+            collect_next(&mut tasks, &mut results).await;
+        }
+        let pending = request(http.clone(), key.clone());
+        tasks.spawn(async move { (key, pending.await) });
+    }
+    while !tasks.is_empty() {
+        collect_next(&mut tasks, &mut results).await;
     }
 
-    /// Fetches the current connection status for all devices.
-    async fn fetch_connections(&mut self) -> Result<ConnectionsResponse, MonitorError> {
-        self.http.get_json("/rest/system/connections").await
+    results
+}
+
+async fn collect_next<R: Send + 'static>(tasks: &mut JoinSet<R>, results: &mut Vec<R>) {
+    match tasks.join_next().await {
+        Some(Ok(result)) => results.push(result),
+        Some(Err(err)) => warn!(error = ?err, "Request task failed"),
+        None => {}
     }
 }
 
+/// Fetches the status of a folder, plus its failed items when it reports any.
+async fn fetch_folder(
+    mut http: HttpClient,
+    folder_id: String,
+) -> Result<FolderFetch, MonitorError> {
+    let query = FolderStatusQuery {
+        folder: folder_id.as_str(),
+    };
+    let status: Value = http.get_json_with_query("/rest/db/status", &query).await?;
+    let errors = if has_folder_errors(&status) {
+        match fetch_folder_errors(&mut http, &folder_id).await {
+            Ok(errors) => Some(errors),
+            Err(err) => {
+                warn!(folder = %folder_id, error = ?err, "Failed to fetch folder errors");
+                None
+            }
+        }
+    } else {
+        None
+    };
+    Ok(FolderFetch { status, errors })
+}
+
+/// Fetches a bounded list of items that failed to sync in a folder.
+async fn fetch_folder_errors(
+    http: &mut HttpClient,
+    folder_id: &str,
+) -> Result<Vec<FolderItemError>, MonitorError> {
+    let query = FolderErrorsQuery {
+        folder: folder_id,
+        page: 1,
+        perpage: FOLDER_ERRORS_LIMIT,
+    };
+    let response: FolderErrorsResponse = http
+        .get_json_with_query("/rest/folder/errors", &query)
+        .await?;
+    Ok(response
+        .errors
+        .unwrap_or_default()
+        .into_iter()
+        .take(FOLDER_ERRORS_LIMIT as usize)
+        .map(FolderItemError::from)
+        .collect())
+}
+
+/// Queries the remote completion status for a specific folder and device.
+async fn query_remote_completion(
+    mut http: HttpClient,
+    (folder_id, device_id): (String, String),
+) -> Result<RemoteCompletion, MonitorError> {
+    let query = CompletionQuery {
+        folder: folder_id.as_str(),
+        device: device_id.as_str(),
+    };
+    http.get_json_with_query("/rest/db/completion", &query)
+        .await
+}
+
+/// Whether a folder status reports failed items or a folder-level error.
+fn has_folder_errors(status: &Value) -> bool {
+    let pull_errors = status
+        .get("pullErrors")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+    let state_error = status
+        .get("state")
+        .and_then(|v| v.as_str())
+        .map(|state| state.eq_ignore_ascii_case("error"))
+        .unwrap_or(false);
+    pull_errors > 0 || state_error
+}
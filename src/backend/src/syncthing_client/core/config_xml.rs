@@ -0,0 +1,136 @@
+use std::path::PathBuf;
+
+use roxmltree::Document;
+use tokio::fs;
+
+use crate::types::MonitorError;
+
+/// Address Syncthing's GUI listens on when none is configured
+const DEFAULT_GUI_HOST: &str = "127.0.0.1";
+const DEFAULT_GUI_PORT: u16 = 8384;
+
+/// The `<gui>` section of Syncthing's `config.xml`, which also governs the REST API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuiConfig {
+    pub enabled: bool,
+    /// Whether plain HTTP is redirected to HTTPS
+    pub tls: bool,
+    pub address: Option<String>,
+    pub api_key: Option<String>,
+}
+
+impl Default for GuiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            tls: false,
+            address: None,
+            api_key: None,
+        }
+    }
+}
+
+/// Where the GUI listens, as configured in `<address>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuiAddress {
+    /// Host to connect to, with wildcard binds mapped to loopback
+    Tcp {
+        host: String,
+        port: u16,
+    },
+    Unix(PathBuf),
+}
+
+impl GuiConfig {
+    pub async fn load(path: &str) -> Result<Self, MonitorError> {
+        let contents = fs::read_to_string(path).await?;
+        Self::parse(&contents)
+    }
+
+    pub fn parse(xml: &str) -> Result<Self, MonitorError> {
+        let document = Document::parse(xml).map_err(|err| invalid(err.to_string()))?;
+        let root = document.root_element();
+        if !root.has_tag_name("configuration") {
+            return Err(invalid(format!(
+                "unexpected root element <{}>",
+                root.tag_name().name()
+            )));
+        }
+        let gui = root
+            .children()
+            .find(|node| node.has_tag_name("gui"))
+            .ok_or_else(|| invalid("no <gui> element".to_string()))?;
+
+        let flag = |name: &str| {
+            gui.attribute(name)
+                .map(|value| value.trim().eq_ignore_ascii_case("true"))
+        };
+        let text = |name: &str| {
+            gui.children()
+                .find(|node| node.has_tag_name(name))
+                .and_then(|node| node.text())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        Ok(GuiConfig {
+            enabled: flag("enabled").unwrap_or(true),
+            tls: flag("tls").unwrap_or(false),
+            address: text("address"),
+            api_key: text("apikey"),
+        })
+    }
+
+    /// Interprets the configured address the way Syncthing does: a path is a
+    /// unix socket, and an unspecified host listens on every interface.
+    pub fn gui_address(&self) -> Result<GuiAddress, MonitorError> {
+        let Some(address) = self.address.as_deref() else {
+            return Ok(GuiAddress::Tcp {
+                host: DEFAULT_GUI_HOST.to_string(),
+                port: DEFAULT_GUI_PORT,
+            });
+        };
+        if let Some(path) = address.strip_prefix("unix://") {
+            return Ok(GuiAddress::Unix(PathBuf::from(path)));
+        }
+        if address.starts_with('/') {
+            return Ok(GuiAddress::Unix(PathBuf::from(address)));
+        }
+
+        let (host, port) = match address.rsplit_once(':') {
+            Some((host, port)) => {
+                let port = port
+                    .parse()
+                    .map_err(|_| invalid(format!("invalid GUI port in {address}")))?;
+                (host, port)
+            }
+            None => (address, DEFAULT_GUI_PORT),
+        };
+        let host = match host.trim_start_matches('[').trim_end_matches(']') {
+            "" | "0.0.0.0" => DEFAULT_GUI_HOST.to_string(),
+            "::" => "::1".to_string(),
+            other => other.to_string(),
+        };
+        Ok(GuiAddress::Tcp { host, port })
+    }
+
+    /// Base URLs to reach a TCP listener on, HTTPS first. Syncthing's GUI
+    /// listener speaks TLS even when `tls` is off; it only redirects plain
+    /// HTTP when it is on.
+    pub fn base_urls(&self, host: &str, port: u16) -> Vec<String> {
+        let authority = if host.contains(':') {
+            format!("[{host}]:{port}")
+        } else {
+            format!("{host}:{port}")
+        };
+        let mut urls = vec![format!("https://{authority}")];
+        if !self.tls {
+            urls.push(format!("http://{authority}"));
+        }
+        urls
+    }
+}
+
+fn invalid(reason: String) -> MonitorError {
+    MonitorError::Config(format!("Invalid Syncthing config.xml: {reason}"))
+}
@@ -0,0 +1,229 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::types::MonitorError;
+
+use super::super::helpers::CONFLICT_SCAN_LIMIT;
+use super::super::models::ConflictPayload;
+
+/// Marker Syncthing inserts into the name of conflict copies.
+const CONFLICT_MARKER: &str = ".sync-conflict-";
+/// Syncthing metadata directories that never contain user conflicts.
+const SKIPPED_DIRS: &[&str] = &[".stfolder", ".stversions"];
+
+/// Which copy to keep when resolving a conflict.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictResolution {
+    /// Delete the conflict copy and keep the current file.
+    Local,
+    /// Replace the current file with the conflict copy.
+    Conflict,
+    /// Keep both by renaming the conflict copy to a regular file name.
+    Both,
+}
+
+/// Parsed parts of a `name.sync-conflict-YYYYMMDD-HHMMSS-DEVICE.ext` file name.
+struct ConflictName<'a> {
+    stem: &'a str,
+    date: &'a str,
+    time: &'a str,
+    device: &'a str,
+    extension: &'a str,
+}
+
+impl<'a> ConflictName<'a> {
+    fn parse(file_name: &'a str) -> Option<Self> {
+        let marker = file_name.find(CONFLICT_MARKER)?;
+        let stem = &file_name[..marker];
+        let rest = &file_name[marker + CONFLICT_MARKER.len()..];
+        let (tag, extension) = match rest.find('.') {
+            Some(dot) => rest.split_at(dot),
+            None => (rest, ""),
+        };
+
+        let mut parts = tag.splitn(3, '-');
+        let date = parts.next().filter(|part| is_digits(part, 8))?;
+        let time = parts.next().filter(|part| is_digits(part, 6))?;
+        let device = parts.next().unwrap_or("");
+        Some(Self {
+            stem,
+            date,
+            time,
+            device,
+            extension,
+        })
+    }
+
+    fn original_name(&self) -> String {
+        format!("{}{}", self.stem, self.extension)
+    }
+
+    /// Human readable timestamp, e.g. `2024-01-31 14:05:09`.
+    fn timestamp(&self) -> String {
+        format!(
+            "{}-{}-{} {}:{}:{}",
+            &self.date[..4],
+            &self.date[4..6],
+            &self.date[6..],
+            &self.time[..2],
+            &self.time[2..4],
+            &self.time[4..]
+        )
+    }
+
+    /// Regular file name used when keeping both copies.
+    fn kept_name(&self, attempt: usize) -> String {
+        let suffix = if attempt > 1 {
+            format!(" {attempt}")
+        } else {
+            String::new()
+        };
+        let device = if self.device.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", self.device)
+        };
+        format!(
+            "{} (conflict {}-{}{}{}){}",
+            self.stem, self.date, self.time, device, suffix, self.extension
+        )
+    }
+}
+
+/// Walks a folder on disk and collects its conflict copies.
+/// Returns the conflicts found and whether the scan stopped at the limit.
+pub fn scan_folder(root: &Path) -> Result<(Vec<ConflictPayload>, bool), MonitorError> {
+    let mut conflicts = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            // The root must be readable; unreadable subdirectories are skipped
+            Err(err) if dir == root => return Err(err.into()),
+            Err(_) => continue,
+        };
+
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let file_name = entry.file_name();
+            let Some(name) = file_name.to_str() else {
+                continue;
+            };
+
+            if file_type.is_dir() {
+                if !SKIPPED_DIRS.contains(&name) {
+                    pending.push(entry.path());
+                }
+                continue;
+            }
+            if !file_type.is_file() {
+                continue;
+            }
+            let Some(parsed) = ConflictName::parse(name) else {
+                continue;
+            };
+
+            let path = entry.path();
+            let original = path.with_file_name(parsed.original_name());
+            let size = entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+            conflicts.push(ConflictPayload {
+                path: relative_display(root, &path),
+                original_path: relative_display(root, &original),
+                original_exists: original.is_file(),
+                device: Some(parsed.device.to_string()).filter(|device| !device.is_empty()),
+                conflict_time: parsed.timestamp(),
+                size,
+            });
+
+            if conflicts.len() >= CONFLICT_SCAN_LIMIT {
+                conflicts.sort_by(|a, b| a.path.cmp(&b.path));
+                return Ok((conflicts, true));
+            }
+        }
+    }
+
+    conflicts.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok((conflicts, false))
+}
+
+/// Resolves a single conflict copy given by its path relative to the folder root.
+/// Returns a short description of what was done.
+pub fn resolve_conflict(
+    root: &Path,
+    conflict_path: &str,
+    resolution: ConflictResolution,
+) -> Result<String, MonitorError> {
+    let relative = sanitize_relative(conflict_path)?;
+    let conflict = root.join(&relative);
+    let name = conflict
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    let parsed = ConflictName::parse(name).ok_or_else(|| {
+        MonitorError::Config(format!("{conflict_path} is not a sync-conflict file"))
+    })?;
+    if !conflict.is_file() {
+        return Err(MonitorError::Config(format!(
+            "Conflict file {conflict_path} no longer exists"
+        )));
+    }
+
+    match resolution {
+        ConflictResolution::Local => {
+            fs::remove_file(&conflict)?;
+            Ok(format!("Removed conflict copy {conflict_path}"))
+        }
+        ConflictResolution::Conflict => {
+            let original = conflict.with_file_name(parsed.original_name());
+            fs::rename(&conflict, &original)?;
+            Ok(format!(
+                "Replaced {} with conflict copy",
+                relative_display(root, &original)
+            ))
+        }
+        ConflictResolution::Both => {
+            let mut attempt = 1;
+            let mut target = conflict.with_file_name(parsed.kept_name(attempt));
+            while target.exists() {
+                attempt += 1;
+                target = conflict.with_file_name(parsed.kept_name(attempt));
+            }
+            fs::rename(&conflict, &target)?;
+            Ok(format!(
+                "Kept conflict copy as {}",
+                relative_display(root, &target)
+            ))
+        }
+    }
+}
+
+/// Rejects absolute paths and parent components so resolutions stay inside the folder.
+fn sanitize_relative(path: &str) -> Result<PathBuf, MonitorError> {
+    let relative = Path::new(path.trim());
+    let safe = relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)));
+    if !safe || relative.as_os_str().is_empty() {
+        return Err(MonitorError::Config(format!(
+            "Invalid conflict path: {path}"
+        )));
+    }
+    Ok(relative.to_path_buf())
+}
+
+fn is_digits(value: &str, len: usize) -> bool {
+    value.len() == len && value.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn relative_display(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .into_owned()
+}
@@ -0,0 +1,38 @@
+use crate::types::MonitorError;
+
+use super::super::helpers::UNREACHABLE_AFTER_FAILURES;
+use super::super::models::{ConnectionState, ConnectionStatus};
+use super::super::SyncthingClient;
+
+/// Tracks consecutive failures to reach the Syncthing API across client
+/// instances, which are dropped and rediscovered after errors.
+#[derive(Debug, Default)]
+pub struct ConnectionTracker {
+    failures: u32,
+    last_error: Option<String>,
+}
+
+impl ConnectionTracker {
+    pub fn record_success(&mut self) {
+        self.failures = 0;
+        self.last_error = None;
+    }
+
+    pub fn record_failure(&mut self, err: &MonitorError) {
+        self.failures = self.failures.saturating_add(1);
+        self.last_error = Some(err.to_string());
+    }
+
+    pub fn status(&self, client: Option<&SyncthingClient>) -> ConnectionStatus {
+        if self.failures >= UNREACHABLE_AFTER_FAILURES {
+            return ConnectionStatus::new(ConnectionState::Unreachable, self.last_error.clone());
+        }
+        if self.failures > 0 {
+            return ConnectionStatus::new(ConnectionState::Connecting, self.last_error.clone());
+        }
+        match client {
+            Some(client) => client.connection_status(),
+            None => ConnectionStatus::new(ConnectionState::Connecting, None),
+        }
+    }
+}
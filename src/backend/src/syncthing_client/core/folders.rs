@@ -0,0 +1,142 @@
+use crate::types::MonitorError;
+
+use super::super::api::{
+    FolderConfig, FolderConfigPatch, FolderDevice, FolderType, SyncthingConfig,
+};
+use super::super::helpers::{default_folder_path, encode_path_segment};
+use super::http::HttpClient;
+
+/// Creates, edits and removes shared folders through the Syncthing config API.
+pub struct FolderManager<'a> {
+    http: &'a mut HttpClient,
+}
+
+impl<'a> FolderManager<'a> {
+    pub fn new(http: &'a mut HttpClient) -> Self {
+        Self { http }
+    }
+
+    /// Creates a new folder shared with the given devices.
+    /// Uses a path under the default folder root when `path` is None.
+    /// Returns the path the folder was created at.
+    pub async fn create(
+        &mut self,
+        folder_id: &str,
+        label: Option<&str>,
+        path: Option<&str>,
+        folder_type: Option<FolderType>,
+        device_ids: &[String],
+    ) -> Result<String, MonitorError> {
+        let folder_id = folder_id.trim();
+        if folder_id.is_empty() {
+            return Err(MonitorError::Config(
+                "Folder ID must not be empty".to_string(),
+            ));
+        }
+
+        let config: SyncthingConfig = self.http.get_json("/rest/config").await?;
+        if config.folders.iter().any(|folder| folder.id == folder_id) {
+            return Err(MonitorError::Config(format!(
+                "Folder {folder_id} already exists"
+            )));
+        }
+        let devices = known_devices(&config, device_ids)?;
+
+        let label = label
+            .map(str::trim)
+            .filter(|label| !label.is_empty())
+            .unwrap_or(folder_id);
+        let path = match path.map(str::trim).filter(|path| !path.is_empty()) {
+            Some(path) => validate_path(path)?,
+            None => default_folder_path(label),
+        };
+
+        let folder = FolderConfig {
+            id: folder_id.to_string(),
+            label: Some(label.to_string()),
+            path: Some(path.clone()),
+            folder_type,
+            paused: None,
+            devices,
+        };
+        self.http.post_json("/rest/config/folders", &folder).await?;
+        Ok(path)
+    }
+
+    /// Updates the label, type and/or device list of an existing folder.
+    /// Fields left as None are not changed.
+    pub async fn update(
+        &mut self,
+        folder_id: &str,
+        label: Option<&str>,
+        folder_type: Option<FolderType>,
+        device_ids: Option<&[String]>,
+    ) -> Result<(), MonitorError> {
+        let config: SyncthingConfig = self.http.get_json("/rest/config").await?;
+        if !config.folders.iter().any(|folder| folder.id == folder_id) {
+            return Err(MonitorError::Config(format!(
+                "Folder {folder_id} does not exist"
+            )));
+        }
+
+        let patch = FolderConfigPatch {
+            label: label.map(|label| label.trim().to_string()),
+            folder_type,
+            devices: match device_ids {
+                Some(ids) => Some(known_devices(&config, ids)?),
+                None => None,
+            },
+        };
+        self.http.patch_json(&folder_path(folder_id), &patch).await
+    }
+
+    /// Removes a folder from the configuration. Files on disk are left in place.
+    pub async fn delete(&mut self, folder_id: &str) -> Result<(), MonitorError> {
+        self.http.delete(&folder_path(folder_id)).await
+    }
+}
+
+/// Builds the config endpoint path for a single folder.
+fn folder_path(folder_id: &str) -> String {
+    format!("/rest/config/folders/{}", encode_path_segment(folder_id))
+}
+
+/// Maps device IDs to folder devices, rejecting IDs that are not configured.
+fn known_devices(
+    config: &SyncthingConfig,
+    device_ids: &[String],
+) -> Result<Vec<FolderDevice>, MonitorError> {
+    let mut devices: Vec<FolderDevice> = Vec::new();
+    for device_id in device_ids {
+        if !config
+            .devices
+            .iter()
+            .any(|device| &device.device_id == device_id)
+        {
+            return Err(MonitorError::Config(format!("Unknown device {device_id}")));
+        }
+        if devices.iter().any(|device| &device.device_id == device_id) {
+            continue;
+        }
+        devices.push(FolderDevice {
+            device_id: device_id.clone(),
+        });
+    }
+    Ok(devices)
+}
+
+/// Ensures a folder path is absolute and not the root directory.
+fn validate_path(path: &str) -> Result<String, MonitorError> {
+    if !path.starts_with('/') {
+        return Err(MonitorError::Config(format!(
+            "Folder path must be absolute: {path}"
+        )));
+    }
+    let trimmed = path.trim_end_matches('/');
+    if trimmed.is_empty() {
+        return Err(MonitorError::Config(
+            "Folder path must not be the root directory".to_string(),
+        ));
+    }
+    Ok(trimmed.to_string())
+}
@@ -1,16 +1,40 @@
-use reqwest::Client;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
+
+use reqwest::{Client, RequestBuilder, Response};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use tracing::{debug, info};
 
 use crate::types::MonitorError;
 
+use super::super::helpers::PREFERRED_URL_RETRY_SECS;
+
 /// Handles low-level HTTP communication with the Syncthing API.
+///
+/// Requests go to the base URL that last worked. On a connection error the
+/// next base URL is tried, and after a failover the preferred (first) URL
+/// is retried every `PREFERRED_URL_RETRY_SECS`. Clones share which URL
+/// works, so a failover learned by one applies to all of them.
 #[derive(Clone)]
 pub struct HttpClient {
     pub(super) api_key: String,
     pub(super) http: Client,
-    pub(super) base_urls: Vec<String>,
-    pub(super) current_idx: usize,
+    pub(super) base_urls: Arc<Vec<String>>,
+    selection: Arc<Mutex<Selection>>,
+}
+
+/// The base URL requests go to, shared by all clones of a client
+#[derive(Default)]
+struct Selection {
+    current_idx: usize,
+    failover: Option<Failover>,
+}
+
+/// Why requests moved off the preferred base URL, and when it was last probed
+struct Failover {
+    reason: String,
+    at: Instant,
 }
 
 impl HttpClient {
@@ -32,29 +56,9 @@ impl HttpClient {
         T: DeserializeOwned,
         Q: Serialize + ?Sized,
     {
-        let base = &self.base_urls[self.current_idx.min(self.base_urls.len().saturating_sub(1))];
-        let url = format!(
-            "{}/{}",
-            base.trim_end_matches('/'),
-            path.trim_start_matches('/')
-        );
         let response = self
-            .http
-            .get(url)
-            .header("X-API-Key", &self.api_key)
-            .query(query)
-            .send()
-            .await
-            .map_err(MonitorError::Http)?;
-
-        if !response.status().is_success() {
-            return Err(MonitorError::Syncthing(format!(
-                "{} returned {}",
-                path,
-                response.status()
-            )));
-        }
-
+            .send(path, |http, url| http.get(url).query(query))
+            .await?;
         response.json::<T>().await.map_err(MonitorError::Http)
     }
 
@@ -63,51 +67,77 @@ impl HttpClient {
     where
         T: Serialize,
     {
-        let base = &self.base_urls[self.current_idx.min(self.base_urls.len().saturating_sub(1))];
-        let url = format!("{}/{}", base.trim_end_matches('/'), path.trim_start_matches('/'));
+        self.send(path, |http, url| http.put(url).json(body))
+            .await?;
+        Ok(())
+    }
 
-        let response = self
-            .http
-            .put(url)
-            .header("X-API-Key", &self.api_key)
-            .header("Content-Type", "application/json")
-            .json(body)
-            .send()
-            .await
-            .map_err(MonitorError::Http)?;
-
-        if !response.status().is_success() {
-            return Err(MonitorError::Syncthing(format!(
-                "{} returned {}",
-                path,
-                response.status()
-            )));
-        }
+    /// Performs a PATCH request with a JSON body.
+    pub async fn patch_json<T>(&mut self, path: &str, body: &T) -> Result<(), MonitorError>
+    where
+        T: Serialize,
+    {
+        self.send(path, |http, url| http.patch(url).json(body))
+            .await?;
+        Ok(())
+    }
 
+    /// Performs a POST request with a JSON body.
+    pub async fn post_json<T>(&mut self, path: &str, body: &T) -> Result<(), MonitorError>
+    where
+        T: Serialize,
+    {
+        self.send(path, |http, url| http.post(url).json(body))
+            .await?;
         Ok(())
     }
 
+    /// Performs a POST request with query parameters and a JSON body,
+    /// and deserializes the JSON response.
+    pub async fn post_json_with_query<T, Q, R>(
+        &mut self,
+        path: &str,
+        query: &Q,
+        body: &T,
+    ) -> Result<R, MonitorError>
+    where
+        T: Serialize,
+        Q: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        let response = self
+            .send(path, |http, url| http.post(url).query(query).json(body))
+            .await?;
+        response.json::<R>().await.map_err(MonitorError::Http)
+    }
+
     /// Performs a POST request with an empty body.
     pub async fn post(&mut self, path: &str) -> Result<(), MonitorError> {
-        let base = &self.base_urls[self.current_idx.min(self.base_urls.len().saturating_sub(1))];
-        let url = format!("{}/{}", base.trim_end_matches('/'), path.trim_start_matches('/'));
+        self.post_with_query(path, &()).await
+    }
 
-        let response = self
-            .http
-            .post(url)
-            .header("X-API-Key", &self.api_key)
-            .send()
-            .await
-            .map_err(MonitorError::Http)?;
-
-        if !response.status().is_success() {
-            return Err(MonitorError::Syncthing(format!(
-                "{} returned {}",
-                path,
-                response.status()
-            )));
-        }
+    /// Performs a POST request with query parameters and an empty body.
+    pub async fn post_with_query<Q>(&mut self, path: &str, query: &Q) -> Result<(), MonitorError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.send(path, |http, url| http.post(url).query(query))
+            .await?;
+        Ok(())
+    }
 
+    /// Performs a DELETE request.
+    pub async fn delete(&mut self, path: &str) -> Result<(), MonitorError> {
+        self.delete_with_query(path, &()).await
+    }
+
+    /// Performs a DELETE request with query parameters.
+    pub async fn delete_with_query<Q>(&mut self, path: &str, query: &Q) -> Result<(), MonitorError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.send(path, |http, url| http.delete(url).query(query))
+            .await?;
         Ok(())
     }
 
@@ -116,9 +146,121 @@ impl HttpClient {
         Self {
             api_key,
             http,
-            base_urls,
-            current_idx: 0,
+            base_urls: Arc::new(base_urls),
+            selection: Arc::default(),
+        }
+    }
+
+    /// Creates a client that sends requests with `http`, sharing the base
+    /// URLs and the URL selection of this one.
+    pub fn with_client(&self, http: Client) -> Self {
+        Self {
+            http,
+            ..self.clone()
+        }
+    }
+
+    /// The base URL requests currently go to.
+    pub fn active_base_url(&self) -> Option<String> {
+        let idx = self.selection().current_idx;
+        self.base_urls.get(idx).cloned()
+    }
+
+    /// Why requests no longer go to the preferred base URL, if they don't.
+    pub fn failover_reason(&self) -> Option<String> {
+        self.selection()
+            .failover
+            .as_ref()
+            .map(|failover| failover.reason.clone())
+    }
+
+    fn selection(&self) -> MutexGuard<'_, Selection> {
+        self.selection
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Sends a request built by `build`, failing over to the next base URL
+    /// on connection errors, and checks the response status.
+    async fn send<F>(&mut self, path: &str, build: F) -> Result<Response, MonitorError>
+    where
+        F: Fn(&Client, String) -> RequestBuilder,
+    {
+        let count = self.base_urls.len();
+        if count == 0 {
+            return Err(MonitorError::Syncthing(
+                "No Syncthing API URL configured".to_string(),
+            ));
         }
+        let start = {
+            let mut selection = self.selection();
+            let current_idx = selection.current_idx.min(count - 1);
+            match selection.failover.as_mut() {
+                // Only one request probes the preferred URL per interval
+                Some(failover) if failover.at.elapsed().as_secs() >= PREFERRED_URL_RETRY_SECS => {
+                    failover.at = Instant::now();
+                    0
+                }
+                _ => current_idx,
+            }
+        };
+
+        let mut attempt = 0;
+        loop {
+            let idx = (start + attempt) % count;
+            let url = join_url(&self.base_urls[idx], path);
+            let result = build(&self.http, url)
+                .header("X-API-Key", &self.api_key)
+                .send()
+                .await;
+            match result {
+                Ok(response) => {
+                    self.switch_to(idx);
+                    return check_status(path, response);
+                }
+                Err(err) if err.is_connect() && attempt + 1 < count => {
+                    debug!(url = %self.base_urls[idx], error = %err, "Syncthing API URL unreachable");
+                    if idx == 0 {
+                        self.selection().failover = Some(Failover {
+                            reason: format!("{} unreachable: {err}", self.base_urls[0]),
+                            at: Instant::now(),
+                        });
+                    }
+                    attempt += 1;
+                }
+                Err(err) => return Err(MonitorError::Http(err)),
+            }
+        }
+    }
+
+    fn switch_to(&self, idx: usize) {
+        let mut selection = self.selection();
+        if idx == 0 {
+            if selection.failover.take().is_some() {
+                info!(url = %self.base_urls[0], "Syncthing API reachable on preferred URL again");
+            }
+        } else if idx != selection.current_idx {
+            info!(url = %self.base_urls[idx], "Failed over to Syncthing API URL");
+        }
+        selection.current_idx = idx;
     }
 }
 
+fn join_url(base: &str, path: &str) -> String {
+    format!(
+        "{}/{}",
+        base.trim_end_matches('/'),
+        path.trim_start_matches('/')
+    )
+}
+
+fn check_status(path: &str, response: Response) -> Result<Response, MonitorError> {
+    if !response.status().is_success() {
+        return Err(MonitorError::Syncthing(format!(
+            "{} returned {}",
+            path,
+            response.status()
+        )));
+    }
+    Ok(response)
+}
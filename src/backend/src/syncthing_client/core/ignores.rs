@@ -0,0 +1,374 @@
+use std::fs;
+use std::path::Path;
+
+use crate::types::MonitorError;
+
+use super::super::helpers::{IGNORE_PREVIEW_LIMIT, IGNORE_PREVIEW_SCAN_LIMIT};
+use super::super::models::IgnoresPreviewPayload;
+
+/// Files Syncthing manages itself and never reports as ignored.
+const INTERNAL_NAMES: &[&str] = &[".stfolder", ".stversions", ".stignore"];
+
+/// A single compiled `.stignore` line.
+struct IgnorePattern {
+    globs: Vec<Vec<char>>,
+    include: bool,
+    case_insensitive: bool,
+}
+
+/// Evaluates `.stignore` patterns the way Syncthing does: first match wins,
+/// `!` re-includes, unanchored patterns match at any depth and a matching
+/// directory also matches everything below it.
+#[derive(Default)]
+pub struct IgnoreMatcher {
+    patterns: Vec<IgnorePattern>,
+}
+
+impl IgnoreMatcher {
+    /// Compiles a pattern list. Returns the matcher and warnings for lines that
+    /// cannot be previewed, or one error message per invalid line.
+    pub fn parse(lines: &[String]) -> Result<(Self, Vec<String>), Vec<String>> {
+        let mut patterns = Vec::new();
+        let mut warnings = Vec::new();
+        let mut errors = Vec::new();
+
+        for (index, raw) in lines.iter().enumerate() {
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+            if line.starts_with("#include") || line.starts_with("#escape") {
+                warnings.push(format!(
+                    "line {}: {line} is not evaluated in the preview",
+                    index + 1
+                ));
+                continue;
+            }
+            match compile_line(line) {
+                Ok(pattern) => patterns.push(pattern),
+                Err(err) => errors.push(format!("line {}: {err}", index + 1)),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok((Self { patterns }, warnings))
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Whether a path relative to the folder root is ignored.
+    pub fn is_ignored(&self, path: &str) -> bool {
+        let exact: Vec<char> = path.chars().collect();
+        let lower: Vec<char> = path.to_lowercase().chars().collect();
+        for pattern in &self.patterns {
+            let text = if pattern.case_insensitive {
+                &lower
+            } else {
+                &exact
+            };
+            if pattern.globs.iter().any(|glob| glob_match(glob, text)) {
+                return !pattern.include;
+            }
+        }
+        false
+    }
+}
+
+fn compile_line(line: &str) -> Result<IgnorePattern, String> {
+    let mut rest = line;
+    let mut include = false;
+    let mut case_insensitive = false;
+    loop {
+        if let Some(stripped) = rest.strip_prefix('!') {
+            include = true;
+            rest = stripped;
+        } else if let Some(stripped) = rest.strip_prefix("(?i)") {
+            case_insensitive = true;
+            rest = stripped;
+        } else if let Some(stripped) = rest.strip_prefix("(?d)") {
+            rest = stripped;
+        } else {
+            break;
+        }
+    }
+
+    let anchored = rest.starts_with('/');
+    let body = rest.trim_start_matches('/').trim_end_matches('/');
+    if body.is_empty() {
+        return Err(format!("{line} does not match anything"));
+    }
+    validate_glob(body).map_err(|err| format!("{line}: {err}"))?;
+
+    let body = if case_insensitive {
+        body.to_lowercase()
+    } else {
+        body.to_string()
+    };
+    let mut bases = vec![body.clone()];
+    if !anchored {
+        bases.push(format!("**/{body}"));
+    }
+    let globs = bases
+        .into_iter()
+        .flat_map(|base| [format!("{base}/**"), base])
+        .map(|glob| glob.chars().collect())
+        .collect();
+
+    Ok(IgnorePattern {
+        globs,
+        include,
+        case_insensitive,
+    })
+}
+
+fn validate_glob(glob: &str) -> Result<(), String> {
+    let mut chars = glob.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.next().is_none() => {
+                return Err("trailing escape character".to_string());
+            }
+            '[' if !chars.any(|c| c == ']') => {
+                return Err("unterminated character class".to_string());
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Matches a glob against a path. `*` and `?` stop at `/`, `**` crosses directories.
+fn glob_match(glob: &[char], text: &[char]) -> bool {
+    match glob.first() {
+        None => text.is_empty(),
+        Some('*') if glob.get(1) == Some(&'*') => {
+            let rest = &glob[2..];
+            // "**/" also matches zero directories
+            if rest.first() == Some(&'/') && glob_match(&rest[1..], text) {
+                return true;
+            }
+            (0..=text.len()).any(|skip| glob_match(rest, &text[skip..]))
+        }
+        Some('*') => {
+            let rest = &glob[1..];
+            for skip in 0..=text.len() {
+                if glob_match(rest, &text[skip..]) {
+                    return true;
+                }
+                if text.get(skip) == Some(&'/') {
+                    break;
+                }
+            }
+            false
+        }
+        Some('?') => match text.first() {
+            Some(c) if *c != '/' => glob_match(&glob[1..], &text[1..]),
+            _ => false,
+        },
+        Some('[') => {
+            let Some(close) = glob.iter().skip(1).position(|c| *c == ']') else {
+                return false;
+            };
+            let class = &glob[1..close + 1];
+            match text.first() {
+                Some(c) if *c != '/' && class_matches(class, *c) => {
+                    glob_match(&glob[close + 2..], &text[1..])
+                }
+                _ => false,
+            }
+        }
+        Some('\\') if glob.len() > 1 => {
+            text.first() == Some(&glob[1]) && glob_match(&glob[2..], &text[1..])
+        }
+        Some(c) => text.first() == Some(c) && glob_match(&glob[1..], &text[1..]),
+    }
+}
+
+fn class_matches(class: &[char], c: char) -> bool {
+    let (negated, class) = match class.first() {
+        Some('!') | Some('^') => (true, &class[1..]),
+        _ => (false, class),
+    };
+    let mut matched = false;
+    let mut i = 0;
+    while i < class.len() {
+        if i + 2 < class.len() && class[i + 1] == '-' {
+            matched |= class[i] <= c && c <= class[i + 2];
+            i += 3;
+        } else {
+            matched |= class[i] == c;
+            i += 1;
+        }
+    }
+    matched != negated
+}
+
+/// Walks a folder on disk and lists files whose ignored state differs between
+/// the current and the proposed patterns.
+pub fn preview_changes(
+    root: &Path,
+    current: &IgnoreMatcher,
+    proposed: &IgnoreMatcher,
+) -> Result<IgnoresPreviewPayload, MonitorError> {
+    let mut preview = IgnoresPreviewPayload::default();
+    let mut pending = vec![String::new()];
+
+    'walk: while let Some(dir) = pending.pop() {
+        let entries = match fs::read_dir(root.join(&dir)) {
+            Ok(entries) => entries,
+            Err(err) if dir.is_empty() => return Err(err.into()),
+            Err(_) => continue,
+        };
+
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let file_name = entry.file_name();
+            let Some(name) = file_name.to_str() else {
+                continue;
+            };
+            if dir.is_empty() && INTERNAL_NAMES.contains(&name) {
+                continue;
+            }
+            let path = if dir.is_empty() {
+                name.to_string()
+            } else {
+                format!("{dir}/{name}")
+            };
+
+            if file_type.is_dir() {
+                pending.push(path);
+                continue;
+            }
+
+            if preview.scanned >= IGNORE_PREVIEW_SCAN_LIMIT {
+                preview.truncated = true;
+                break 'walk;
+            }
+            preview.scanned += 1;
+            let was_ignored = current.is_ignored(&path);
+            let now_ignored = proposed.is_ignored(&path);
+            let list = match (was_ignored, now_ignored) {
+                (false, true) => &mut preview.newly_ignored,
+                (true, false) => &mut preview.newly_unignored,
+                _ => continue,
+            };
+            if list.len() < IGNORE_PREVIEW_LIMIT {
+                list.push(path);
+            } else {
+                preview.truncated = true;
+            }
+        }
+    }
+
+    preview.newly_ignored.sort();
+    preview.newly_unignored.sort();
+    Ok(preview)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matcher(lines: &[&str]) -> IgnoreMatcher {
+        let lines: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        IgnoreMatcher::parse(&lines).unwrap().0
+    }
+
+    fn glob(glob: &str, text: &str) -> bool {
+        let glob: Vec<char> = glob.chars().collect();
+        let text: Vec<char> = text.chars().collect();
+        glob_match(&glob, &text)
+    }
+
+    #[test]
+    fn leading_slash_anchors_pattern() {
+        let anchored = matcher(&["/foo"]);
+        assert!(anchored.is_ignored("foo"));
+        assert!(!anchored.is_ignored("sub/foo"));
+
+        let unanchored = matcher(&["foo/bar"]);
+        assert!(unanchored.is_ignored("foo/bar"));
+        assert!(unanchored.is_ignored("a/b/foo/bar"));
+        assert!(!unanchored.is_ignored("foo/bar2"));
+    }
+
+    #[test]
+    fn single_star_stays_within_directory() {
+        assert!(glob("*.pdf", "notes.pdf"));
+        assert!(!glob("*.pdf", "books/notes.pdf"));
+        assert!(glob("a/*/c", "a/b/c"));
+        assert!(!glob("a/*/c", "a/b/x/c"));
+        assert!(glob("?.txt", "a.txt"));
+        assert!(!glob("?", "/"));
+    }
+
+    #[test]
+    fn double_star_crosses_directories() {
+        assert!(glob("**/c", "c"));
+        assert!(glob("**/c", "a/b/c"));
+        assert!(glob("a/**/c", "a/c"));
+        assert!(glob("a/**/c", "a/b/x/c"));
+        assert!(glob("a/**", "a/b/c"));
+    }
+
+    #[test]
+    fn matching_directory_matches_contents() {
+        let matcher = matcher(&["/Books", "cache"]);
+        assert!(matcher.is_ignored("Books/novel.epub"));
+        assert!(matcher.is_ignored("Books/2024/novel.epub"));
+        assert!(matcher.is_ignored("app/cache/data.bin"));
+        assert!(!matcher.is_ignored("Bookshelf/novel.epub"));
+    }
+
+    #[test]
+    fn first_match_wins() {
+        let matcher = matcher(&["!/keep.pdf", "*.pdf", "!other.pdf"]);
+        assert!(!matcher.is_ignored("keep.pdf"));
+        assert!(matcher.is_ignored("sub/keep.pdf"));
+        assert!(matcher.is_ignored("other.pdf"));
+        assert!(!matcher.is_ignored("notes.txt"));
+    }
+
+    #[test]
+    fn case_insensitive_prefix() {
+        let matcher = matcher(&["(?i)*.PDF", "Notes"]);
+        assert!(matcher.is_ignored("Book.pdf"));
+        assert!(matcher.is_ignored("BOOK.Pdf"));
+        assert!(matcher.is_ignored("Notes"));
+        assert!(!matcher.is_ignored("notes"));
+    }
+
+    #[test]
+    fn character_classes() {
+        assert!(glob("[a-c].txt", "b.txt"));
+        assert!(!glob("[a-c].txt", "d.txt"));
+        assert!(glob("[!x].txt", "y.txt"));
+        assert!(!glob("[!x].txt", "x.txt"));
+        assert!(glob("[xyz]", "z"));
+    }
+
+    #[test]
+    fn backslash_escapes() {
+        assert!(glob(r"\*.txt", "*.txt"));
+        assert!(!glob(r"\*.txt", "a.txt"));
+        assert!(glob(r"a\[b]", "a[b]"));
+    }
+
+    #[test]
+    fn reports_invalid_lines_and_unsupported_directives() {
+        let lines: Vec<String> = ["#include more", "[abc", "ok", "/"]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        let errors = IgnoreMatcher::parse(&lines).err().unwrap();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].starts_with("line 2:"));
+        assert!(errors[1].starts_with("line 4:"));
+
+        let (_, warnings) = IgnoreMatcher::parse(&lines[..1]).unwrap();
+        assert_eq!(warnings.len(), 1);
+    }
+}
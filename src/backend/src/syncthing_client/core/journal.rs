@@ -0,0 +1,292 @@
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+use tracing::warn;
+
+use crate::types::MonitorError;
+
+use super::super::api::SyncthingEvent;
+use super::super::helpers::{
+    JOURNAL_COMPACT_SLACK, JOURNAL_DEDUP_WINDOW_SECS, JOURNAL_MAX_ENTRIES, JOURNAL_PAGE_LIMIT,
+    JOURNAL_RETENTION_DAYS,
+};
+use super::super::models::ChangeRecord;
+
+struct JournalEntry {
+    at: DateTime<Utc>,
+    record: ChangeRecord,
+}
+
+/// What folding a change into the journal did
+enum Merge {
+    New,
+    Duplicate,
+    /// A recorded change gained details, and needs to be written again
+    Updated(ChangeRecord),
+}
+
+/// Per-folder history of file changes, persisted as JSON lines in the app directory.
+///
+/// Fed from `ItemFinished` events of the main event stream and from the
+/// `LocalChangeDetected`/`RemoteChangeDetected` events of `/rest/events/disk`.
+/// The same change reported by both, or replayed after a reconnect, is only
+/// recorded once; details learned later are appended as a new line that
+/// replaces the earlier one on load. Entries older than the retention period
+/// or beyond the entry limit are dropped and the file is compacted once
+/// enough of it is stale.
+#[derive(Default)]
+pub struct ChangeJournal {
+    path: Option<PathBuf>,
+    entries: VecDeque<JournalEntry>,
+    lines_on_disk: usize,
+}
+
+impl ChangeJournal {
+    /// Loads the journal from `path`, or keeps it in memory only when no path is known.
+    pub async fn load(path: Option<PathBuf>) -> Self {
+        let Some(path) = path else {
+            return Self::default();
+        };
+
+        let read_path = path.clone();
+        let contents =
+            match tokio::task::spawn_blocking(move || fs::read_to_string(&read_path)).await {
+                Ok(Ok(contents)) => contents,
+                Ok(Err(err)) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+                Ok(Err(err)) => {
+                    warn!(path = %path.display(), error = ?err, "Failed to read change journal");
+                    String::new()
+                }
+                Err(err) => {
+                    warn!(error = ?err, "Change journal load task failed");
+                    String::new()
+                }
+            };
+
+        let mut journal = Self {
+            path: Some(path),
+            ..Self::default()
+        };
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            journal.lines_on_disk += 1;
+            let Ok(record) = serde_json::from_str::<ChangeRecord>(line) else {
+                continue;
+            };
+            let Some(at) = parse_time(&record.time) else {
+                continue;
+            };
+            if let Merge::New = journal.merge_duplicate(at, &record) {
+                journal.entries.push_back(JournalEntry { at, record });
+            }
+        }
+        journal.prune();
+        journal.persist(&[]).await;
+        journal
+    }
+
+    /// Records the file changes contained in a batch of events.
+    pub async fn record(&mut self, events: &[SyncthingEvent]) {
+        let mut added = Vec::new();
+        for record in events.iter().filter_map(change_from_event) {
+            let Some(at) = parse_time(&record.time) else {
+                continue;
+            };
+            match self.merge_duplicate(at, &record) {
+                Merge::New => {
+                    added.push(record.clone());
+                    self.entries.push_back(JournalEntry { at, record });
+                }
+                Merge::Duplicate => {}
+                Merge::Updated(updated) => added.push(updated),
+            }
+        }
+        if added.is_empty() {
+            return;
+        }
+        self.prune();
+        self.persist(&added).await;
+    }
+
+    /// Returns one page of matching changes, newest first, and the total number of matches.
+    /// `device_id` matches changes made by that peer; `page` starts at 1 and
+    /// `per_page` is capped at `JOURNAL_PAGE_LIMIT`.
+    pub fn query(
+        &self,
+        folder_id: Option<&str>,
+        device_id: Option<&str>,
+        since: Option<DateTime<Utc>>,
+        page: usize,
+        per_page: usize,
+    ) -> (Vec<ChangeRecord>, usize) {
+        let mut matches: Vec<&JournalEntry> = self
+            .entries
+            .iter()
+            .filter(|entry| {
+                folder_id
+                    .map(|id| entry.record.folder_id == id)
+                    .unwrap_or(true)
+            })
+            .filter(|entry| {
+                device_id
+                    .map(|id| made_by(&entry.record, id))
+                    .unwrap_or(true)
+            })
+            .filter(|entry| since.map(|since| entry.at >= since).unwrap_or(true))
+            .collect();
+        matches.sort_by_key(|entry| Reverse(entry.at));
+
+        let total = matches.len();
+        let per_page = per_page.clamp(1, JOURNAL_PAGE_LIMIT);
+        let changes = matches
+            .into_iter()
+            .skip(page.saturating_sub(1) * per_page)
+            .take(per_page)
+            .map(|entry| entry.record.clone())
+            .collect();
+        (changes, total)
+    }
+
+    /// Folds a change into an already recorded one for the same item close in time.
+    fn merge_duplicate(&mut self, at: DateTime<Utc>, record: &ChangeRecord) -> Merge {
+        let window = Duration::seconds(JOURNAL_DEDUP_WINDOW_SECS);
+        let Some(existing) = self.entries.iter_mut().rev().find(|entry| {
+            entry.record.folder_id == record.folder_id
+                && entry.record.path == record.path
+                && entry.record.local == record.local
+                && (entry.at - at).abs() <= window
+        }) else {
+            return Merge::New;
+        };
+
+        // Disk events carry the modifying device and item type, item events do not
+        let mut updated = false;
+        if existing.record.modified_by.is_none() && record.modified_by.is_some() {
+            existing.record.modified_by = record.modified_by.clone();
+            updated = true;
+        }
+        if existing.record.item_type.is_none() && record.item_type.is_some() {
+            existing.record.item_type = record.item_type.clone();
+            updated = true;
+        }
+        if updated {
+            Merge::Updated(existing.record.clone())
+        } else {
+            Merge::Duplicate
+        }
+    }
+
+    fn prune(&mut self) {
+        let cutoff = Utc::now() - Duration::days(JOURNAL_RETENTION_DAYS);
+        self.entries.retain(|entry| entry.at >= cutoff);
+        while self.entries.len() > JOURNAL_MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Appends new records to the journal file, or rewrites it when it holds
+    /// too many lines that were pruned or merged since.
+    async fn persist(&mut self, added: &[ChangeRecord]) {
+        let Some(path) = self.path.clone() else {
+            return;
+        };
+
+        let compact = self.lines_on_disk + added.len() > self.entries.len() + JOURNAL_COMPACT_SLACK;
+        let (records, written): (Vec<ChangeRecord>, usize) = if compact {
+            let records: Vec<ChangeRecord> = self
+                .entries
+                .iter()
+                .map(|entry| entry.record.clone())
+                .collect();
+            let written = records.len();
+            (records, written)
+        } else if added.is_empty() {
+            return;
+        } else {
+            (added.to_vec(), self.lines_on_disk + added.len())
+        };
+
+        let result = tokio::task::spawn_blocking(move || write_records(&path, &records, compact))
+            .await
+            .map_err(|err| MonitorError::Io(std::io::Error::other(err)));
+        match result {
+            Ok(Ok(())) => self.lines_on_disk = written,
+            Ok(Err(err)) | Err(err) => warn!(error = ?err, "Failed to write change journal"),
+        }
+    }
+}
+
+/// Appends records to the journal file, or replaces its contents when `rewrite` is set.
+fn write_records(path: &Path, records: &[ChangeRecord], rewrite: bool) -> Result<(), MonitorError> {
+    let mut buffer = String::new();
+    for record in records {
+        buffer.push_str(&serde_json::to_string(record)?);
+        buffer.push('\n');
+    }
+
+    if rewrite {
+        let temp_path = path.with_extension("jsonl.tmp");
+        fs::write(&temp_path, buffer)?;
+        fs::rename(&temp_path, path)?;
+    } else {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.write_all(buffer.as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Extracts a journal record from a file change event, if the event is one.
+fn change_from_event(event: &SyncthingEvent) -> Option<ChangeRecord> {
+    let data = &event.data;
+    let text = |key: &str| data.get(key).and_then(|v| v.as_str()).map(str::to_string);
+
+    match event.event_type.as_str() {
+        "LocalChangeDetected" | "RemoteChangeDetected" => Some(ChangeRecord {
+            time: event.time.clone(),
+            folder_id: text("folder").or_else(|| text("folderID"))?,
+            path: text("path")?,
+            action: text("action").unwrap_or_else(|| "modified".to_string()),
+            item_type: text("type"),
+            local: event.event_type == "LocalChangeDetected",
+            modified_by: text("modifiedBy"),
+        }),
+        "ItemFinished" => {
+            // Failed pulls did not change anything on disk
+            if data.get("error").is_some_and(|error| !error.is_null()) {
+                return None;
+            }
+            let action = match text("action").as_deref() {
+                Some("delete") => "deleted",
+                _ => "modified",
+            };
+            Some(ChangeRecord {
+                time: event.time.clone(),
+                folder_id: text("folder")?,
+                path: text("item")?,
+                action: action.to_string(),
+                item_type: text("type"),
+                local: false,
+                modified_by: None,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Whether a change was made by the given device. Syncthing reports the
+/// modifying device by its short ID, the first block of the full ID.
+fn made_by(record: &ChangeRecord, device_id: &str) -> bool {
+    record
+        .modified_by
+        .as_deref()
+        .is_some_and(|short_id| !short_id.is_empty() && device_id.starts_with(short_id))
+}
+
+fn parse_time(time: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(time)
+        .ok()
+        .map(|parsed| parsed.with_timezone(&Utc))
+}
@@ -1,6 +1,22 @@
 mod aggregator;
+mod config_xml;
+mod conflicts;
+mod connection;
+mod folders;
 mod http;
+mod ignores;
+mod journal;
+mod model;
+mod traffic;
 
 pub use aggregator::DataAggregator;
+pub use config_xml::{GuiAddress, GuiConfig};
+pub use conflicts::{resolve_conflict, scan_folder, ConflictResolution};
+pub use connection::ConnectionTracker;
+pub use folders::FolderManager;
 pub use http::HttpClient;
+pub use ignores::{preview_changes, IgnoreMatcher};
+pub use journal::ChangeJournal;
+pub use model::StatusModel;
+pub use traffic::TrafficTracker;
 
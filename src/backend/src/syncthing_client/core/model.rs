@@ -0,0 +1,419 @@
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use super::super::api::{
+    ConnectionsResponse, DeviceConfig, FolderFileError, RemoteCompletion, SyncthingConfig,
+    SyncthingData, SyncthingEvent,
+};
+use super::super::helpers::{
+    format_relative_time, is_file_event, COMPLETION_CACHE_SECS, FOLDER_ERRORS_LIMIT,
+};
+use super::super::models::{
+    FolderChange, FolderItemError, FolderPayload, FolderPeerNeedSummary, LogEntryPayload,
+    PeerPayload, PeerProgress, PendingDevicePayload, PendingFolderPayload, SyncthingOverview,
+};
+use super::traffic::TrafficSnapshot;
+
+/// Latest file change of a folder, kept with its raw event time so the
+/// relative "when" text stays current between refreshes.
+#[derive(Clone)]
+struct CachedChange {
+    name: String,
+    action: String,
+    time: String,
+    origin: Option<String>,
+}
+
+/// Remote completion of a folder on a peer and when it was last fetched or
+/// reported, so full refetches can skip pairs that are still fresh.
+#[derive(Clone)]
+struct CachedCompletion {
+    completion: RemoteCompletion,
+    updated_at: Instant,
+}
+
+/// In-memory copy of everything the status payload is built from.
+///
+/// A full refetch fills the model on startup or after it went out of sync;
+/// afterwards it is kept current from the event stream and only the cheap
+/// system status and connection counters are polled.
+#[derive(Clone, Default)]
+pub struct StatusModel {
+    synced: bool,
+    pending_dirty: bool,
+    last_event_id: u64,
+    pub(super) status: Value,
+    pub(super) config: SyncthingConfig,
+    pub(super) connections: ConnectionsResponse,
+    pub(super) traffic: TrafficSnapshot,
+    pub(super) folder_status: HashMap<String, Value>,
+    pub(super) folder_errors: HashMap<String, Vec<FolderItemError>>,
+    completions: HashMap<(String, String), CachedCompletion>,
+    unavailable_folders: HashMap<String, String>,
+    pub(super) pending_devices: Vec<PendingDevicePayload>,
+    pub(super) pending_folders: Vec<PendingFolderPayload>,
+    pub(super) system_warnings: Vec<LogEntryPayload>,
+    pub(super) discovery: BTreeMap<String, Value>,
+    latest_changes: HashMap<String, CachedChange>,
+}
+
+impl StatusModel {
+    /// Whether the model reflects Syncthing's state and can be updated incrementally.
+    pub fn is_synced(&self) -> bool {
+        self.synced
+    }
+
+    /// Forces a full refetch on the next refresh.
+    pub fn invalidate(&mut self) {
+        self.synced = false;
+    }
+
+    /// Whether pending devices or folders changed since they were last fetched.
+    pub fn pending_dirty(&self) -> bool {
+        self.pending_dirty
+    }
+
+    /// Clears all cached data before a full refetch.
+    /// Traffic counters and remote completion survive, the latter until it expires.
+    pub(super) fn reset(&mut self) {
+        *self = Self {
+            traffic: std::mem::take(&mut self.traffic),
+            completions: std::mem::take(&mut self.completions),
+            ..Self::default()
+        };
+    }
+
+    /// Drops cached remote completion for folder/peer pairs no longer in the config.
+    pub(super) fn prune_completions(&mut self) {
+        let config = &self.config;
+        self.completions.retain(|(folder_id, device_id), _| {
+            config.folders.iter().any(|folder| {
+                folder.id == *folder_id
+                    && folder
+                        .devices
+                        .iter()
+                        .any(|device| device.device_id == *device_id)
+            })
+        });
+    }
+
+    /// Whether cached remote completion for a folder/peer pair can be reused.
+    pub(super) fn completion_is_fresh(&self, key: &(String, String)) -> bool {
+        self.completions
+            .get(key)
+            .map(|cached| cached.updated_at.elapsed() < Duration::from_secs(COMPLETION_CACHE_SECS))
+            .unwrap_or(false)
+    }
+
+    pub(super) fn store_completion(&mut self, key: (String, String), completion: RemoteCompletion) {
+        self.completions.insert(
+            key,
+            CachedCompletion {
+                completion,
+                updated_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Records that a folder's status could not be fetched; shown as its error
+    /// until the next status update for the folder arrives.
+    pub(super) fn mark_folder_unavailable(&mut self, folder_id: String, message: String) {
+        self.unavailable_folders
+            .insert(folder_id, format!("Status unavailable: {message}"));
+    }
+
+    /// Marks a full refetch as complete; `last_event_id` is the newest event it saw.
+    pub(super) fn mark_synced(&mut self, last_event_id: u64) {
+        self.synced = true;
+        self.pending_dirty = false;
+        self.last_event_id = last_event_id;
+    }
+
+    pub(super) fn set_pending(
+        &mut self,
+        devices: Vec<PendingDevicePayload>,
+        folders: Vec<PendingFolderPayload>,
+    ) {
+        self.pending_devices = devices;
+        self.pending_folders = folders;
+        self.pending_dirty = false;
+    }
+
+    /// Applies a batch of events from the event stream.
+    /// Invalidates the model when events were missed or the config changed.
+    pub fn apply_events(&mut self, events: &[SyncthingEvent]) {
+        for event in events {
+            if !self.synced {
+                return;
+            }
+            if event.id <= self.last_event_id {
+                continue;
+            }
+            if self.last_event_id != 0 && event.id > self.last_event_id + 1 {
+                // The event buffer overflowed or the batch was truncated
+                self.invalidate();
+                return;
+            }
+            self.last_event_id = event.id;
+            self.apply_event(event);
+        }
+    }
+
+    fn apply_event(&mut self, event: &SyncthingEvent) {
+        let data = &event.data;
+        let text = |key: &str| data.get(key).and_then(|v| v.as_str()).map(str::to_string);
+
+        match event.event_type.as_str() {
+            "ConfigSaved" | "Starting" | "StartupComplete" => self.invalidate(),
+            "PendingDevicesChanged" | "PendingFoldersChanged" => self.pending_dirty = true,
+            "FolderSummary" => {
+                if let (Some(folder), Some(summary)) = (text("folder"), data.get("summary")) {
+                    self.unavailable_folders.remove(&folder);
+                    // FolderErrors is only emitted while there are errors
+                    let pull_errors = summary.get("pullErrors").and_then(Value::as_u64);
+                    if pull_errors == Some(0) {
+                        self.folder_errors.remove(&folder);
+                    }
+                    self.folder_status.insert(folder, summary.clone());
+                }
+            }
+            "StateChanged" => {
+                if let (Some(folder), Some(to)) = (text("folder"), text("to")) {
+                    let status = self
+                        .folder_status
+                        .entry(folder)
+                        .or_insert_with(|| Value::Object(Default::default()));
+                    if let Some(status) = status.as_object_mut() {
+                        status.insert("state".to_string(), Value::String(to));
+                    }
+                }
+            }
+            "FolderCompletion" => {
+                let completion = serde_json::from_value::<RemoteCompletion>(data.clone());
+                if let (Some(folder), Some(device), Ok(completion)) =
+                    (text("folder"), text("device"), completion)
+                {
+                    self.store_completion((folder, device), completion);
+                }
+            }
+            "FolderErrors" => {
+                if let Some(folder) = text("folder") {
+                    let errors = data
+                        .get("errors")
+                        .cloned()
+                        .and_then(|errors| {
+                            serde_json::from_value::<Vec<FolderFileError>>(errors).ok()
+                        })
+                        .unwrap_or_default();
+                    self.folder_errors.insert(
+                        folder,
+                        errors
+                            .into_iter()
+                            .take(FOLDER_ERRORS_LIMIT as usize)
+                            .map(FolderItemError::from)
+                            .collect(),
+                    );
+                }
+            }
+            "DeviceConnected" => {
+                if let Some(device) = text("id") {
+                    let state = self.connections.connections.entry(device).or_default();
+                    state.connected = true;
+                    state.address = text("addr").or(state.address.take());
+                    state.client_version = text("clientVersion").or(state.client_version.take());
+                }
+            }
+            "DeviceDisconnected" => {
+                if let Some(state) =
+                    text("id").and_then(|device| self.connections.connections.get_mut(&device))
+                {
+                    state.connected = false;
+                }
+            }
+            "DevicePaused" | "DeviceResumed" => {
+                let paused = event.event_type == "DevicePaused";
+                if let Some(device) = text("device") {
+                    self.connections
+                        .connections
+                        .entry(device)
+                        .or_default()
+                        .paused = paused;
+                }
+            }
+            "FolderPaused" | "FolderResumed" => {
+                let paused = event.event_type == "FolderPaused";
+                if let Some(folder_id) = text("id") {
+                    if let Some(folder) = self
+                        .config
+                        .folders
+                        .iter_mut()
+                        .find(|folder| folder.id == folder_id)
+                    {
+                        folder.paused = Some(paused);
+                    }
+                }
+            }
+            event_type if is_file_event(event_type) => self.record_change(event),
+            _ => {}
+        }
+    }
+
+    /// Remembers the file touched by an event as the latest change of its folder.
+    pub(super) fn record_change(&mut self, event: &SyncthingEvent) {
+        let (Some(folder_id), Some(name)) = (event.folder_id(), event.file_name()) else {
+            return;
+        };
+        self.latest_changes.insert(
+            folder_id.to_string(),
+            CachedChange {
+                name,
+                action: event.action().unwrap_or_else(|| event.event_type.clone()),
+                time: event.time.clone(),
+                origin: event.origin(),
+            },
+        );
+    }
+
+    /// Builds the UI payload from the cached state.
+    pub fn compose(&self) -> SyncthingData {
+        let mut overview = SyncthingOverview::from_value(&self.status);
+        overview.transfer = Some(self.traffic.total.clone());
+        overview.warnings = self.system_warnings.clone();
+        overview.connectivity.set_discovery(&self.discovery);
+        let my_id = overview.my_id.clone();
+
+        let (folder_peer_summaries, peer_progress) = self.peer_metrics(my_id.as_deref());
+        let empty_status = Value::Object(Default::default());
+
+        let folders = self
+            .config
+            .folders
+            .iter()
+            .map(|folder| {
+                // Keep UI contract: a Vec, but only ever include the latest (0..1)
+                let last_changes = self
+                    .latest_changes
+                    .get(&folder.id)
+                    .map(|change| FolderChange {
+                        name: change.name.clone(),
+                        action: change.action.clone(),
+                        when: format_relative_time(&change.time),
+                        origin: change.origin.clone(),
+                    })
+                    .into_iter()
+                    .collect::<Vec<_>>();
+                let mut payload = FolderPayload::from_parts(
+                    folder,
+                    self.folder_status.get(&folder.id).unwrap_or(&empty_status),
+                    self.folder_errors
+                        .get(&folder.id)
+                        .cloned()
+                        .unwrap_or_default(),
+                    last_changes,
+                    folder_peer_summaries.get(&folder.id).copied(),
+                );
+                if payload.error.is_none() {
+                    payload.error = self.unavailable_folders.get(&folder.id).cloned();
+                }
+                payload
+            })
+            .collect();
+
+        let peers = self.compose_peers(&self.config.devices, my_id.as_deref(), &peer_progress);
+
+        SyncthingData {
+            overview,
+            folders,
+            peers,
+            pending_devices: self.pending_devices.clone(),
+            pending_folders: self.pending_folders.clone(),
+        }
+    }
+
+    /// Summarizes cached remote completion per folder and per peer.
+    fn peer_metrics(
+        &self,
+        my_id: Option<&str>,
+    ) -> (
+        HashMap<String, FolderPeerNeedSummary>,
+        HashMap<String, PeerProgress>,
+    ) {
+        let mut folder_summaries: HashMap<String, FolderPeerNeedSummary> = HashMap::new();
+        let mut peer_progress: HashMap<String, PeerProgress> = HashMap::new();
+
+        for folder in &self.config.folders {
+            for device in &folder.devices {
+                if device.device_id.is_empty() || my_id == Some(device.device_id.as_str()) {
+                    continue;
+                }
+                let key = (folder.id.clone(), device.device_id.clone());
+                let Some(remote_completion) = self.completions.get(&key).map(|c| &c.completion)
+                else {
+                    continue;
+                };
+
+                let need = remote_completion.need_bytes.unwrap_or(0);
+                if need > 0 {
+                    let entry = folder_summaries.entry(folder.id.clone()).or_default();
+                    entry.peer_count = entry.peer_count.saturating_add(1);
+                    entry.need_bytes = entry.need_bytes.saturating_add(need);
+                }
+
+                peer_progress
+                    .entry(device.device_id.clone())
+                    .or_default()
+                    .record(folder, remote_completion);
+            }
+        }
+
+        (folder_summaries, peer_progress)
+    }
+
+    /// Builds peer payloads from device configuration and collected metrics.
+    fn compose_peers(
+        &self,
+        devices: &[DeviceConfig],
+        my_id: Option<&str>,
+        peer_progress: &HashMap<String, PeerProgress>,
+    ) -> Vec<PeerPayload> {
+        let mut peers = Vec::new();
+        for device in devices {
+            if device.device_id.is_empty() {
+                continue;
+            }
+            if my_id
+                .map(|local| local == device.device_id.as_str())
+                .unwrap_or(false)
+            {
+                continue;
+            }
+
+            let connection = self.connections.connections.get(&device.device_id);
+            let progress = peer_progress.get(&device.device_id);
+            let paused =
+                device.paused.unwrap_or(false) || connection.map(|c| c.paused).unwrap_or(false);
+
+            peers.push(PeerPayload {
+                id: device.device_id.clone(),
+                name: device
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| device.device_id.clone()),
+                connected: connection.map(|c| c.connected).unwrap_or(false),
+                paused,
+                address: connection.and_then(|c| c.address.clone()),
+                client_version: connection.and_then(|c| c.client_version.clone()),
+                last_seen: connection.and_then(|c| c.last_seen.clone()),
+                completion: progress.and_then(|p| p.avg_completion()),
+                need_bytes: progress.and_then(|p| p.outstanding_need()),
+                transfer: self.traffic.devices.get(&device.device_id).cloned(),
+                folders: progress.map(|p| p.folders.clone()).unwrap_or_default(),
+            });
+        }
+
+        peers.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        peers
+    }
+}
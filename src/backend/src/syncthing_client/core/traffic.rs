@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use super::super::api::ConnectionsResponse;
+use super::super::models::TransferStats;
+
+/// Samples closer together than this keep the previously computed rates,
+/// so bursts of status refreshes do not produce noisy values.
+const MIN_SAMPLE_INTERVAL_SECS: f64 = 1.0;
+
+#[derive(Clone, Copy, Default)]
+struct Counters {
+    in_bytes: u64,
+    out_bytes: u64,
+}
+
+#[derive(Clone)]
+struct TrafficSample {
+    taken_at: Instant,
+    total: Counters,
+    devices: HashMap<String, Counters>,
+}
+
+/// Transfer statistics for the whole instance and for each connected device.
+#[derive(Clone, Default)]
+pub struct TrafficSnapshot {
+    pub total: TransferStats,
+    pub devices: HashMap<String, TransferStats>,
+}
+
+/// Keeps the previous connection counters to derive transfer rates between samples.
+#[derive(Clone, Default)]
+pub struct TrafficTracker {
+    previous: Option<TrafficSample>,
+    last: TrafficSnapshot,
+}
+
+impl TrafficTracker {
+    /// Records the counters from `/rest/system/connections` and returns
+    /// totals plus the rates since the previous sample.
+    pub fn update(&mut self, connections: &ConnectionsResponse) -> TrafficSnapshot {
+        let now = Instant::now();
+        let sample = TrafficSample {
+            taken_at: now,
+            total: Counters {
+                in_bytes: connections.total.in_bytes_total,
+                out_bytes: connections.total.out_bytes_total,
+            },
+            devices: connections
+                .connections
+                .iter()
+                .map(|(device_id, state)| {
+                    let counters = Counters {
+                        in_bytes: state.in_bytes_total,
+                        out_bytes: state.out_bytes_total,
+                    };
+                    (device_id.clone(), counters)
+                })
+                .collect(),
+        };
+
+        let elapsed = self
+            .previous
+            .as_ref()
+            .map(|previous| now.duration_since(previous.taken_at).as_secs_f64());
+        if elapsed.is_some_and(|secs| secs < MIN_SAMPLE_INTERVAL_SECS) {
+            return self.with_totals(&sample);
+        }
+
+        let previous = self.previous.as_ref();
+        let snapshot = TrafficSnapshot {
+            total: stats(sample.total, previous.map(|p| p.total), elapsed),
+            devices: sample
+                .devices
+                .iter()
+                .map(|(device_id, counters)| {
+                    let before = previous.and_then(|p| p.devices.get(device_id).copied());
+                    (device_id.clone(), stats(*counters, before, elapsed))
+                })
+                .collect(),
+        };
+
+        self.previous = Some(sample);
+        self.last = snapshot.clone();
+        snapshot
+    }
+
+    /// Last computed rates combined with the counters of a fresh sample.
+    fn with_totals(&self, sample: &TrafficSample) -> TrafficSnapshot {
+        let mut snapshot = self.last.clone();
+        snapshot.total.in_bytes_total = sample.total.in_bytes;
+        snapshot.total.out_bytes_total = sample.total.out_bytes;
+        for (device_id, counters) in &sample.devices {
+            let entry = snapshot.devices.entry(device_id.clone()).or_default();
+            entry.in_bytes_total = counters.in_bytes;
+            entry.out_bytes_total = counters.out_bytes;
+        }
+        snapshot
+    }
+}
+
+fn stats(current: Counters, previous: Option<Counters>, elapsed: Option<f64>) -> TransferStats {
+    let rate = |now: u64, before: Option<u64>| match (before, elapsed) {
+        // Counters go backwards when Syncthing restarts or a device reconnects
+        (Some(before), Some(secs)) if now >= before && secs > 0.0 => {
+            Some((now - before) as f64 / secs)
+        }
+        _ => None,
+    };
+    TransferStats {
+        in_bytes_total: current.in_bytes,
+        out_bytes_total: current.out_bytes,
+        in_bytes_per_sec: rate(current.in_bytes, previous.map(|p| p.in_bytes)),
+        out_bytes_per_sec: rate(current.out_bytes, previous.map(|p| p.out_bytes)),
+    }
+}
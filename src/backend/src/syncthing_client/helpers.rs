@@ -1,11 +1,33 @@
 use chrono::Utc;
+use reqwest::{Certificate, Url};
 use std::env;
+use std::net::IpAddr;
+use std::path::PathBuf;
 use tokio::fs;
+use tracing::warn;
 
 use crate::config::Config;
-use crate::types::MonitorError;
 
 pub const RECENT_EVENTS_LIMIT: u32 = 200;
+pub const DEFAULT_FOLDER_ROOT: &str = "/home/root";
+pub const FOLDER_ERRORS_LIMIT: u32 = 20;
+pub const CONFLICT_SCAN_LIMIT: usize = 200;
+pub const VERSIONS_LIST_LIMIT: usize = 500;
+pub const IGNORE_PREVIEW_LIMIT: usize = 100;
+pub const IGNORE_PREVIEW_SCAN_LIMIT: usize = 50_000;
+pub const MAX_CONCURRENT_REQUESTS: usize = 4;
+pub const COMPLETION_CACHE_SECS: u64 = 120;
+pub const JOURNAL_MAX_ENTRIES: usize = 5_000;
+pub const JOURNAL_RETENTION_DAYS: i64 = 30;
+pub const JOURNAL_COMPACT_SLACK: usize = 1_000;
+pub const JOURNAL_DEDUP_WINDOW_SECS: i64 = 30;
+pub const JOURNAL_PAGE_LIMIT: usize = 200;
+pub const SYSTEM_LOG_LIMIT: usize = 200;
+pub const RESCAN_ACK_SECS: u64 = 2;
+pub const BANDWIDTH_LIMIT_MAX_KBPS: u64 = 10_000_000;
+pub const PREFERRED_URL_RETRY_SECS: u64 = 60;
+pub const UNREACHABLE_AFTER_FAILURES: u32 = 3;
+pub const GUI_PASSWORD_MIN_LENGTH: usize = 8;
 
 pub fn is_file_event(event_type: &str) -> bool {
     matches!(
@@ -39,25 +61,114 @@ pub fn format_relative_time(iso_time: &str) -> String {
     }
 }
 
-pub async fn load_api_key(config: &Config) -> Result<String, MonitorError> {
-    if let Ok(value) = env::var("SYNCTHING_API_KEY") {
-        if !value.trim().is_empty() {
-            return Ok(value);
+/// Resolves a configured folder path on disk, expanding a leading `~` to the home directory.
+pub fn expand_folder_path(path: &str) -> PathBuf {
+    let home = || env::var("HOME").unwrap_or_else(|_| DEFAULT_FOLDER_ROOT.to_string());
+    match path.strip_prefix('~') {
+        Some("") => PathBuf::from(home()),
+        Some(rest) if rest.starts_with('/') => PathBuf::from(format!("{}{}", home(), rest)),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Suggests a local path under `DEFAULT_FOLDER_ROOT` for a folder with the given label.
+pub fn default_folder_path(label: &str) -> String {
+    let sanitized: String = label
+        .trim()
+        .chars()
+        .map(|c| {
+            if c == '/' || c == '\\' || c.is_control() {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    let name = match sanitized.trim_matches('.') {
+        "" => "Sync",
+        _ => sanitized.as_str(),
+    };
+    format!("{}/{}", DEFAULT_FOLDER_ROOT, name)
+}
+
+/// Turns a GUI listen address into a URL another device can open, over HTTPS when `tls` is set.
+/// Returns None for wildcard binds and unix sockets, whose reachable address is unknown,
+/// and for loopback binds, which other devices cannot reach.
+pub fn gui_url_from_address(address: &str, tls: bool) -> Option<String> {
+    let address = address.trim();
+    if address.is_empty() || address.starts_with('/') || address.starts_with("unix") {
+        return None;
+    }
+    let host = match address.rsplit_once(':') {
+        Some((host, _port)) => host,
+        None => address,
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let loopback = host.eq_ignore_ascii_case("localhost")
+        || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback());
+    let scheme = if tls { "https" } else { "http" };
+    match host {
+        "" | "0.0.0.0" | "::" => None,
+        _ if loopback => None,
+        _ => Some(format!("{scheme}://{address}")),
+    }
+}
+
+/// Whether a base URL points at this device, where plain HTTP never leaves the host.
+pub fn is_loopback_url(url: &str) -> bool {
+    let Ok(parsed) = Url::parse(url) else {
+        return false;
+    };
+    match parsed.host_str() {
+        Some("localhost") => true,
+        Some(host) => host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .map(|ip| ip.is_loopback())
+            .unwrap_or(false),
+        None => false,
+    }
+}
+
+/// Loads the certificate Syncthing serves its GUI and API with, to trust it
+/// and nothing else. Returns None if it does not exist yet or is unreadable.
+pub async fn load_gui_certificate(config: &Config) -> Option<Certificate> {
+    let path = config.syncthing_gui_cert_path();
+    let pem = match fs::read(&path).await {
+        Ok(pem) => pem,
+        Err(err) => {
+            warn!(path = %path, error = ?err, "Syncthing GUI certificate unavailable");
+            return None;
+        }
+    };
+    match Certificate::from_pem(&pem) {
+        Ok(cert) => Some(cert),
+        Err(err) => {
+            warn!(path = %path, error = ?err, "Invalid Syncthing GUI certificate");
+            None
         }
     }
+}
 
-    let config_xml_path = config.syncthing_config_xml_path();
-    let contents = fs::read_to_string(&config_xml_path)
-        .await
-        .map_err(|err| MonitorError::Io(err))?;
-    extract_api_key(&contents).ok_or(MonitorError::MissingApiKey)
+/// Percent-encodes a single URL path segment (e.g. a folder or device ID).
+pub fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            other => encoded.push_str(&format!("%{other:02X}")),
+        }
+    }
+    encoded
 }
 
-fn extract_api_key(contents: &str) -> Option<String> {
-    let start_tag = "<apikey>";
-    let end_tag = "</apikey>";
-    let start = contents.find(start_tag)? + start_tag.len();
-    let rest = &contents[start..];
-    let end = rest.find(end_tag)?;
-    Some(rest[..end].trim().to_string())
+/// Reads an environment variable that overrides a discovered setting.
+pub fn env_override(name: &str) -> Option<String> {
+    env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
 }
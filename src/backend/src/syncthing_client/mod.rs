@@ -4,7 +4,12 @@ mod core;
 mod helpers;
 mod models;
 
+pub use api::{FolderType, SyncthingData};
 pub use client::SyncthingClient;
+pub use core::{ChangeJournal, ConflictResolution, ConnectionTracker};
 
 // Re-export data types at root for convenience
-pub use models::{FolderPayload, PeerPayload, SyncthingOverview};
+pub use models::{
+    ConnectionStatus, FolderPayload, GuiSecurityPayload, PeerPayload, PendingDevicePayload,
+    PendingFolderPayload, SyncthingOverview,
+};
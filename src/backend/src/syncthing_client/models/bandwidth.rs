@@ -0,0 +1,58 @@
+use serde::Serialize;
+
+use crate::syncthing_client::api::{BandwidthOptions, DeviceConfig};
+
+/// Send and receive rate limits in kbit/s; 0 means unlimited.
+#[derive(Debug, Serialize, Clone, Copy, Default)]
+pub struct BandwidthLimitsPayload {
+    pub max_send_kbps: u64,
+    pub max_recv_kbps: u64,
+}
+
+impl BandwidthLimitsPayload {
+    fn from_kbps(send: i64, recv: i64) -> Self {
+        Self {
+            max_send_kbps: send.max(0) as u64,
+            max_recv_kbps: recv.max(0) as u64,
+        }
+    }
+}
+
+impl From<&BandwidthOptions> for BandwidthLimitsPayload {
+    fn from(options: &BandwidthOptions) -> Self {
+        Self::from_kbps(options.max_send_kbps, options.max_recv_kbps)
+    }
+}
+
+/// Rate limits of a single peer, for UI display.
+#[derive(Debug, Serialize, Clone)]
+pub struct DeviceBandwidthPayload {
+    pub device_id: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub limits: BandwidthLimitsPayload,
+}
+
+impl From<&DeviceConfig> for DeviceBandwidthPayload {
+    fn from(device: &DeviceConfig) -> Self {
+        Self {
+            device_id: device.device_id.clone(),
+            name: device
+                .name
+                .clone()
+                .filter(|name| !name.trim().is_empty())
+                .unwrap_or_else(|| device.device_id.clone()),
+            limits: BandwidthLimitsPayload::from_kbps(
+                device.max_send_kbps.unwrap_or(0),
+                device.max_recv_kbps.unwrap_or(0),
+            ),
+        }
+    }
+}
+
+/// Global and per-peer rate limits.
+#[derive(Debug, Serialize, Clone)]
+pub struct BandwidthPayload {
+    pub global: BandwidthLimitsPayload,
+    pub devices: Vec<DeviceBandwidthPayload>,
+}
@@ -0,0 +1,24 @@
+use serde::Serialize;
+
+/// A sync-conflict copy found on disk, with paths relative to the folder root.
+#[derive(Debug, Serialize, Clone)]
+pub struct ConflictPayload {
+    pub path: String,
+    pub original_path: String,
+    pub original_exists: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    pub conflict_time: String,
+    pub size: u64,
+}
+
+/// Conflict copies found in a single folder, for UI display.
+#[derive(Debug, Serialize, Clone)]
+pub struct FolderConflictsPayload {
+    pub folder_id: String,
+    pub label: String,
+    pub conflicts: Vec<ConflictPayload>,
+    pub truncated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
@@ -0,0 +1,32 @@
+use serde::Serialize;
+
+/// How well the backend can reach the Syncthing API.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionState {
+    /// Not connected yet, or reconnecting after a failure
+    Connecting,
+    Connected,
+    /// Reachable only through a fallback base URL
+    Degraded,
+    /// Still failing after several attempts
+    Unreachable,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct ConnectionStatus {
+    pub state: ConnectionState,
+    pub reason: Option<String>,
+    /// Base URL requests currently go to
+    pub base_url: Option<String>,
+}
+
+impl ConnectionStatus {
+    pub fn new(state: ConnectionState, reason: Option<String>) -> Self {
+        Self {
+            state,
+            reason,
+            base_url: None,
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Part of Syncthing's connectivity that can fail independently.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectivityKind {
+    Listener,
+    Relay,
+    GlobalDiscovery,
+    LocalDiscovery,
+}
+
+/// OK or error state of a single listener, relay or discovery service.
+#[derive(Debug, Serialize, Clone)]
+pub struct ConnectivityComponent {
+    pub kind: ConnectivityKind,
+    pub name: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub addresses: Vec<String>,
+}
+
+/// Addresses global or local discovery currently knows for a device.
+#[derive(Debug, Serialize, Clone)]
+pub struct DiscoveredPeer {
+    pub device_id: String,
+    pub addresses: Vec<String>,
+}
+
+/// Connectivity diagnostics: listeners, relays, discovery services and the discovery cache.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ConnectivityPayload {
+    pub components: Vec<ConnectivityComponent>,
+    pub discovered_peers: Vec<DiscoveredPeer>,
+}
+
+impl ConnectivityPayload {
+    /// Reads the `connectionServiceStatus` and `discoveryStatus` sections of `/rest/system/status`.
+    pub fn from_status(status: &Value) -> Self {
+        let mut components = Vec::new();
+
+        for (name, state) in sections(status, "connectionServiceStatus") {
+            let kind = if name.starts_with("relay") || name.starts_with("dynamic+") {
+                ConnectivityKind::Relay
+            } else {
+                ConnectivityKind::Listener
+            };
+            let addresses = ["lanAddresses", "wanAddresses"]
+                .iter()
+                .filter_map(|key| state.get(*key).and_then(|v| v.as_array()))
+                .flatten()
+                .filter_map(|v| v.as_str())
+                .map(str::to_string)
+                .collect();
+            components.push(ConnectivityComponent::new(kind, name, state, addresses));
+        }
+
+        for (name, state) in sections(status, "discoveryStatus") {
+            let kind = if name.starts_with("global@") {
+                ConnectivityKind::GlobalDiscovery
+            } else {
+                ConnectivityKind::LocalDiscovery
+            };
+            components.push(ConnectivityComponent::new(kind, name, state, Vec::new()));
+        }
+
+        Self {
+            components,
+            discovered_peers: Vec::new(),
+        }
+    }
+
+    /// Fills in the discovery cache from `/rest/system/discovery`, keyed by device ID.
+    pub fn set_discovery(&mut self, discovery: &BTreeMap<String, Value>) {
+        self.discovered_peers = discovery
+            .iter()
+            .map(|(device_id, entry)| DiscoveredPeer {
+                device_id: device_id.clone(),
+                addresses: entry
+                    .get("addresses")
+                    .and_then(|v| v.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|v| v.as_str())
+                    .map(str::to_string)
+                    .collect(),
+            })
+            .collect();
+    }
+}
+
+impl ConnectivityComponent {
+    fn new(kind: ConnectivityKind, name: &str, state: &Value, addresses: Vec<String>) -> Self {
+        let error = state
+            .get("error")
+            .and_then(|v| v.as_str())
+            .filter(|error| !error.is_empty())
+            .map(str::to_string);
+        Self {
+            kind,
+            name: name.to_string(),
+            ok: error.is_none(),
+            error,
+            addresses,
+        }
+    }
+}
+
+/// Entries of an object-valued section of the system status.
+fn sections<'a>(status: &'a Value, key: &str) -> impl Iterator<Item = (&'a str, &'a Value)> {
+    status
+        .get(key)
+        .and_then(|v| v.as_object())
+        .into_iter()
+        .flatten()
+        .map(|(name, state)| (name.as_str(), state))
+}
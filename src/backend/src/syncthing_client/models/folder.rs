@@ -1,7 +1,7 @@
 use serde::Serialize;
 use serde_json::Value;
 
-use crate::syncthing_client::api::FolderConfig;
+use crate::syncthing_client::api::{FolderConfig, FolderFileError, FolderType};
 
 /// Represents the current state of a folder in a human-readable format.
 #[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
@@ -31,6 +31,9 @@ pub struct FolderPayload {
     pub id: String,
     pub label: String,
     pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub folder_type: Option<FolderType>,
+    pub shared_with: Vec<String>,
     pub state: String,
     pub state_code: FolderStateCode,
     pub state_raw: Option<String>,
@@ -39,6 +42,11 @@ pub struct FolderPayload {
     pub in_sync_bytes: Option<u64>,
     pub need_bytes: Option<u64>,
     pub completion: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub pull_errors: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub file_errors: Vec<FolderItemError>,
     pub last_changes: Vec<FolderChange>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub peers_need_summary: Option<FolderPeerNeedSummary>,
@@ -53,6 +61,22 @@ pub struct FolderChange {
     pub origin: Option<String>,
 }
 
+/// A single file or directory that failed to sync.
+#[derive(Debug, Serialize, Clone)]
+pub struct FolderItemError {
+    pub path: String,
+    pub error: String,
+}
+
+impl From<FolderFileError> for FolderItemError {
+    fn from(value: FolderFileError) -> Self {
+        Self {
+            path: value.path,
+            error: value.error,
+        }
+    }
+}
+
 /// Summary of how many peers need data from this folder.
 #[derive(Debug, Serialize, Clone, Copy, Default)]
 pub struct FolderPeerNeedSummary {
@@ -79,6 +103,7 @@ impl FolderPayload {
     pub fn from_parts(
         folder: &FolderConfig,
         status: &Value,
+        file_errors: Vec<FolderItemError>,
         last_changes: Vec<FolderChange>,
         peers_need_summary: Option<FolderPeerNeedSummary>,
     ) -> Self {
@@ -90,6 +115,15 @@ impl FolderPayload {
             .get("state")
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
+        let error = status
+            .get("error")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string());
+        let pull_errors = status
+            .get("pullErrors")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
         let paused = folder.paused.unwrap_or(false);
         let state_info = humanize_folder_state(paused, state_raw.as_deref(), need_bytes);
 
@@ -97,6 +131,12 @@ impl FolderPayload {
             id: folder.id.clone(),
             label: folder.label.clone().unwrap_or_else(|| folder.id.clone()),
             path: folder.path.clone(),
+            folder_type: folder.folder_type,
+            shared_with: folder
+                .devices
+                .iter()
+                .map(|device| device.device_id.clone())
+                .collect(),
             state: state_info.label,
             state_code: state_info.code,
             state_raw,
@@ -105,6 +145,9 @@ impl FolderPayload {
             in_sync_bytes,
             need_bytes,
             completion,
+            error,
+            pull_errors,
+            file_errors,
             last_changes,
             peers_need_summary,
         }
@@ -0,0 +1,24 @@
+use serde::Serialize;
+
+use crate::syncthing_client::api::GuiSettings;
+
+/// Whether the GUI asks for a login and is served over HTTPS, for UI display.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct GuiSecurityPayload {
+    pub auth_configured: bool,
+    pub user: Option<String>,
+    pub tls: bool,
+}
+
+impl From<&GuiSettings> for GuiSecurityPayload {
+    fn from(settings: &GuiSettings) -> Self {
+        let user = Some(settings.user.trim())
+            .filter(|user| !user.is_empty())
+            .map(|user| user.to_string());
+        Self {
+            auth_configured: user.is_some() && !settings.password.is_empty(),
+            user,
+            tls: settings.use_tls,
+        }
+    }
+}
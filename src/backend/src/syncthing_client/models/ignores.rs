@@ -0,0 +1,11 @@
+use serde::Serialize;
+
+/// Files whose ignored state would change with a new set of ignore patterns.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct IgnoresPreviewPayload {
+    pub newly_ignored: Vec<String>,
+    pub newly_unignored: Vec<String>,
+    pub scanned: usize,
+    pub truncated: bool,
+    pub warnings: Vec<String>,
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// A single file change recorded in the on-disk change journal.
+/// Serialized as one JSON line per change and sent to the UI as-is.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChangeRecord {
+    pub time: String,
+    pub folder_id: String,
+    pub path: String,
+    pub action: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_type: Option<String>,
+    /// Whether the change was made on this device rather than pulled from a peer.
+    pub local: bool,
+    /// Short ID of the device that made the change, when Syncthing reports it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified_by: Option<String>,
+}
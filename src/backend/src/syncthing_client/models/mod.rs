@@ -1,8 +1,30 @@
+mod bandwidth;
+mod conflict;
+mod connection;
+mod connectivity;
 mod folder;
+mod gui_security;
+mod ignores;
+mod journal;
 mod overview;
 mod peer;
+mod pending;
+mod system_log;
+mod traffic;
+mod versions;
 
-pub use folder::{FolderChange, FolderPayload, FolderPeerNeedSummary};
+pub use bandwidth::BandwidthPayload;
+pub use conflict::{ConflictPayload, FolderConflictsPayload};
+pub use connection::{ConnectionState, ConnectionStatus};
+pub use connectivity::ConnectivityPayload;
+pub use folder::{FolderChange, FolderItemError, FolderPayload, FolderPeerNeedSummary};
+pub use gui_security::GuiSecurityPayload;
+pub use ignores::IgnoresPreviewPayload;
+pub use journal::ChangeRecord;
 pub use overview::SyncthingOverview;
 pub use peer::{PeerPayload, PeerProgress};
+pub use pending::{PendingDevicePayload, PendingFolderPayload};
+pub use system_log::{LogEntryPayload, LogSeverity};
+pub use traffic::TransferStats;
+pub use versions::FileVersionsPayload;
 
@@ -1,6 +1,8 @@
 use serde::Serialize;
 use serde_json::Value;
 
+use super::{ConnectivityPayload, LogEntryPayload, TransferStats};
+
 /// Aggregated system status information from Syncthing.
 #[derive(Debug, Serialize, Default)]
 pub struct SyncthingOverview {
@@ -13,7 +15,12 @@ pub struct SyncthingOverview {
     pub uptime_seconds: Option<f64>,
     pub sequence: Option<u64>,
     pub goroutine_count: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer: Option<TransferStats>,
     pub errors: Vec<String>,
+    /// Warnings Syncthing currently reports, e.g. insufficient disk space.
+    pub warnings: Vec<LogEntryPayload>,
+    pub connectivity: ConnectivityPayload,
 }
 
 impl SyncthingOverview {
@@ -49,7 +56,10 @@ impl SyncthingOverview {
                 .or_else(|| value.get("dbSequence"))
                 .and_then(|v| v.as_u64()),
             goroutine_count: value.get("goroutineCount").and_then(|v| v.as_u64()),
+            transfer: None,
             errors: Vec::new(),
+            warnings: Vec::new(),
+            connectivity: ConnectivityPayload::from_status(value),
         }
     }
 
@@ -2,6 +2,8 @@ use serde::Serialize;
 
 use crate::syncthing_client::api::{FolderConfig, RemoteCompletion};
 
+use super::TransferStats;
+
 /// Represents the sync state of a folder as seen from a peer's perspective.
 #[derive(Debug, Serialize, Clone, Default)]
 pub struct PeerFolderState {
@@ -30,6 +32,8 @@ pub struct PeerPayload {
     pub completion: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub need_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer: Option<TransferStats>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub folders: Vec<PeerFolderState>,
 }
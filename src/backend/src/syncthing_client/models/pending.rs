@@ -0,0 +1,74 @@
+use serde::Serialize;
+
+use crate::syncthing_client::api::{DeviceConfig, PendingDevice, PendingFolder};
+use crate::syncthing_client::helpers::default_folder_path;
+
+/// A device waiting to be accepted or dismissed, for UI display.
+#[derive(Debug, Serialize, Clone)]
+pub struct PendingDevicePayload {
+    pub id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+}
+
+impl PendingDevicePayload {
+    pub fn from_parts(device_id: &str, pending: &PendingDevice) -> Self {
+        Self {
+            id: device_id.to_string(),
+            name: pending
+                .name
+                .clone()
+                .filter(|name| !name.trim().is_empty())
+                .unwrap_or_else(|| device_id.to_string()),
+            address: pending.address.clone(),
+            time: pending.time.clone(),
+        }
+    }
+}
+
+/// A folder share offered by remote devices, for UI display.
+#[derive(Debug, Serialize, Clone)]
+pub struct PendingFolderPayload {
+    pub id: String,
+    pub label: String,
+    pub default_path: String,
+    pub offered_by: Vec<PendingFolderOfferPayload>,
+}
+
+/// A single device offering a pending folder.
+#[derive(Debug, Serialize, Clone)]
+pub struct PendingFolderOfferPayload {
+    pub device_id: String,
+    pub device_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+}
+
+impl PendingFolderPayload {
+    pub fn from_parts(folder_id: &str, pending: &PendingFolder, devices: &[DeviceConfig]) -> Self {
+        let label = pending.label().unwrap_or(folder_id).to_string();
+        let offered_by = pending
+            .offered_by
+            .iter()
+            .map(|(device_id, offer)| PendingFolderOfferPayload {
+                device_id: device_id.clone(),
+                device_name: devices
+                    .iter()
+                    .find(|device| &device.device_id == device_id)
+                    .and_then(|device| device.name.clone())
+                    .unwrap_or_else(|| device_id.clone()),
+                time: offer.time.clone(),
+            })
+            .collect();
+
+        Self {
+            id: folder_id.to_string(),
+            default_path: default_folder_path(&label),
+            label,
+            offered_by,
+        }
+    }
+}
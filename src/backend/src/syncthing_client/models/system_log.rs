@@ -0,0 +1,45 @@
+use serde::Serialize;
+
+use crate::syncthing_client::api::SystemLogLine;
+
+/// Severity of a Syncthing log line or warning.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogSeverity {
+    Debug,
+    Info,
+    Warning,
+    Error,
+}
+
+impl LogSeverity {
+    /// Maps a Syncthing log level. Syncthing 1.x uses 0-3 for debug, verbose,
+    /// info and warning; 2.x uses the slog levels -4, 0, 4 and 8.
+    fn from_level(level: i64) -> Self {
+        match level {
+            8.. => Self::Error,
+            3.. => Self::Warning,
+            0.. => Self::Info,
+            _ => Self::Debug,
+        }
+    }
+}
+
+/// A line of Syncthing's log or one of its current warnings, for UI display.
+#[derive(Debug, Serialize, Clone)]
+pub struct LogEntryPayload {
+    pub when: String,
+    pub message: String,
+    pub severity: LogSeverity,
+}
+
+impl LogEntryPayload {
+    /// Builds an entry, using `fallback` when the line carries no level.
+    pub fn from_line(line: SystemLogLine, fallback: LogSeverity) -> Self {
+        Self {
+            when: line.when,
+            message: line.message,
+            severity: line.level.map(LogSeverity::from_level).unwrap_or(fallback),
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use serde::Serialize;
+
+/// Byte counters and transfer rates since the previous status sample.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct TransferStats {
+    pub in_bytes_total: u64,
+    pub out_bytes_total: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_bytes_per_sec: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out_bytes_per_sec: Option<f64>,
+}
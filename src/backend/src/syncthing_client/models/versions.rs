@@ -0,0 +1,34 @@
+use serde::Serialize;
+
+use crate::syncthing_client::api::FileVersion;
+
+/// A single stored version of a file, for UI display.
+#[derive(Debug, Serialize, Clone)]
+pub struct VersionPayload {
+    pub version_time: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mod_time: Option<String>,
+    pub size: u64,
+}
+
+/// All stored versions of one file, newest first.
+#[derive(Debug, Serialize, Clone)]
+pub struct FileVersionsPayload {
+    pub path: String,
+    pub versions: Vec<VersionPayload>,
+}
+
+impl FileVersionsPayload {
+    pub fn from_parts(path: String, versions: Vec<FileVersion>) -> Self {
+        let mut versions: Vec<VersionPayload> = versions
+            .into_iter()
+            .map(|version| VersionPayload {
+                version_time: version.version_time,
+                mod_time: version.mod_time,
+                size: version.size,
+            })
+            .collect();
+        versions.sort_by(|a, b| b.version_time.cmp(&a.version_time));
+        Self { path, versions }
+    }
+}
@@ -3,7 +3,14 @@ use thiserror::Error;
 
 // Re-export types from other modules for convenience
 pub use crate::systemd::SystemdStatus;
-pub use crate::syncthing_client::{FolderPayload, PeerPayload, SyncthingOverview};
+pub use crate::syncthing_client::{
+    ConnectionStatus, FolderPayload, GuiSecurityPayload, PeerPayload, PendingDevicePayload,
+    PendingFolderPayload, SyncthingOverview,
+};
+
+use crate::network::NetworkStatus;
+use crate::power::PowerStatus;
+use crate::scheduler::ScheduleStatus;
 
 #[derive(Debug, Error)]
 pub enum MonitorError {
@@ -17,6 +24,8 @@ pub enum MonitorError {
     Http(#[from] reqwest::Error),
     #[error("json error: {0}")]
     Json(#[from] serde_json::Error),
+    #[error("qr code error: {0}")]
+    QrCode(#[from] qrcode::types::QrError),
     #[error("Syncthing API key not found")]
     MissingApiKey,
     #[error("config error: {0}")]
@@ -29,8 +38,15 @@ pub struct StatusPayload {
     pub reason: String,
     pub systemd: SystemdStatus,
     pub syncthing: SyncthingOverview,
+    pub connection: ConnectionStatus,
     pub folders: Vec<FolderPayload>,
     pub peers: Vec<PeerPayload>,
+    pub pending_devices: Vec<PendingDevicePayload>,
+    pub pending_folders: Vec<PendingFolderPayload>,
     pub gui_address: Option<String>,
+    pub gui_security: Option<GuiSecurityPayload>,
+    pub schedule: Option<ScheduleStatus>,
+    pub power: Option<PowerStatus>,
+    pub network: Option<NetworkStatus>,
 }
 
@@ -1,5 +1,7 @@
+pub mod backoff;
 pub mod filesystem;
 pub mod formatting;
+pub mod qr;
 pub mod systemctl;
 
 pub use formatting::format_bytes;
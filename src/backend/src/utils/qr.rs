@@ -0,0 +1,47 @@
+//! QR code rendering for pairing, so the frontend only has to draw modules.
+
+use qrcode::render::svg;
+use qrcode::{Color, EcLevel, QrCode};
+use serde::Serialize;
+
+use crate::types::MonitorError;
+
+const SVG_MIN_DIMENSION: u32 = 256;
+
+/// A rendered QR code as a module matrix plus an equivalent SVG document.
+#[derive(Debug, Serialize, Clone)]
+pub struct QrMatrix {
+    pub content: String,
+    /// Number of modules per side (excluding the quiet zone).
+    pub size: usize,
+    /// One string per row, `1` for a dark module and `0` for a light one.
+    pub modules: Vec<String>,
+    pub svg: String,
+}
+
+/// Encodes `content` as a QR code with medium error correction.
+pub fn render_qr(content: &str) -> Result<QrMatrix, MonitorError> {
+    let code = QrCode::with_error_correction_level(content.as_bytes(), EcLevel::M)?;
+    let size = code.width();
+    let modules = code
+        .to_colors()
+        .chunks(size)
+        .map(|row| {
+            row.iter()
+                .map(|color| if *color == Color::Dark { '1' } else { '0' })
+                .collect()
+        })
+        .collect();
+    let svg = code
+        .render::<svg::Color>()
+        .min_dimensions(SVG_MIN_DIMENSION, SVG_MIN_DIMENSION)
+        .quiet_zone(true)
+        .build();
+
+    Ok(QrMatrix {
+        content: content.to_string(),
+        size,
+        modules,
+        svg,
+    })
+}
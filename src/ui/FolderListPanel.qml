@@ -294,6 +294,34 @@ Item {
                                 color: "#4f566a"
                             }
 
+                            Text {
+                                visible: !!modelData.error || (modelData.file_errors || []).length > 0
+                                text: modelData.pull_errors > 0 ? `Errors (${modelData.pull_errors} items failed)` : "Errors"
+                                font.pointSize: fs(16)
+                                font.bold: true
+                                color: "#8a1f1f"
+                            }
+
+                            Text {
+                                visible: !!modelData.error
+                                text: modelData.error || ""
+                                font.pointSize: fs(14)
+                                color: "#8a1f1f"
+                                wrapMode: Text.Wrap
+                                width: folderCard.width - 40
+                            }
+
+                            Repeater {
+                                model: (modelData.file_errors || []).slice(0, 5)
+                                delegate: Text {
+                                    text: `${modelData.path} · ${modelData.error}`
+                                    font.pointSize: fs(14)
+                                    color: "#8a1f1f"
+                                    elide: Text.ElideRight
+                                    width: folderCard.width - 40
+                                }
+                            }
+
                             Item {
                                 width: parent.width
                                 height: folderCard.expanded ? 8 : 0
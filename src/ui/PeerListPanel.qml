@@ -45,6 +45,19 @@ Item {
         return numeric.toFixed(2) + "%"
     }
 
+    function formatRate(value) {
+        if (value === undefined || value === null)
+            return "n/a"
+        return formatBytes(value) + "/s"
+    }
+
+    function transferSummary(peer) {
+        if (!peer || !peer.transfer || !peer.connected)
+            return ""
+        var transfer = peer.transfer
+        return `↓ ${formatRate(transfer.in_bytes_per_sec)} · ↑ ${formatRate(transfer.out_bytes_per_sec)}`
+    }
+
     function formatTimeAgo(value) {
         if (!value)
             return "unknown"
@@ -199,6 +212,20 @@ Item {
                                 color: "#232a40"
                                 visible: modelData.need_bytes !== undefined
                             }
+
+                            Text {
+                                text: "·"
+                                font.pointSize: fs(16)
+                                color: "#232a40"
+                                visible: peersPanel.transferSummary(modelData).length > 0
+                            }
+
+                            Text {
+                                text: peersPanel.transferSummary(modelData)
+                                font.pointSize: fs(16)
+                                color: "#232a40"
+                                visible: text.length > 0
+                            }
                         }
 
                         Item {
@@ -234,6 +261,13 @@ Item {
                                     color: "#2b3146"
                                     visible: !!modelData.client_version
                                 }
+
+                                Text {
+                                    text: modelData.transfer ? `Received ${peersPanel.formatBytes(modelData.transfer.in_bytes_total)} · Sent ${peersPanel.formatBytes(modelData.transfer.out_bytes_total)}` : ""
+                                    font.pointSize: fs(14)
+                                    color: "#2b3146"
+                                    visible: !!modelData.transfer
+                                }
                             }
 
                             Column {
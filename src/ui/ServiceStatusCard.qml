@@ -7,6 +7,10 @@ Rectangle {
     property real fontScale: 1.0
     property var serviceStatus: ({})
     property var syncthingStatus: ({})
+    property var scheduleStatus: null
+    property var powerStatus: null
+    property var networkStatus: null
+    property var connectionStatus: null
     property bool controlBusy: false
     property var installerStatus: null
     property bool installerAttentionRequired: false
@@ -14,6 +18,7 @@ Rectangle {
 
     signal controlRequested(string action)
     signal settingsRequested()
+    signal clearWarningsRequested()
 
     Layout.fillWidth: true
     Layout.preferredHeight: contentColumn.implicitHeight + 40
@@ -47,14 +52,71 @@ Rectangle {
         return primary
     }
 
+    function warningColor(severity) {
+        return severity === "error" ? "#8a1f1f" : "#8a5a00"
+    }
+
+    function scheduleSummary() {
+        if (!scheduleStatus)
+            return ""
+        const state = scheduleStatus.sync_allowed ? "Sync window open" : "Outside sync window"
+        if (!scheduleStatus.next_transition)
+            return state
+        const next = new Date(scheduleStatus.next_transition)
+        const verb = scheduleStatus.sync_allowed ? "closes" : "opens"
+        return state + " \u2013 " + verb + " " + next.toLocaleString(Qt.locale(), "ddd hh:mm")
+    }
+
+    function powerSummary() {
+        if (!powerStatus || powerStatus.capacity_percent === null || powerStatus.capacity_percent === undefined)
+            return ""
+        let text = "Battery " + powerStatus.capacity_percent + "%"
+        if (powerStatus.charging)
+            text += ", charging"
+        if (powerStatus.sync_blocked)
+            text += " \u2013 sync suspended until " + powerStatus.policy.resume_above_percent + "% or charging"
+        return text
+    }
+
+    function networkSummary() {
+        if (!networkStatus || !networkStatus.policy_enabled)
+            return ""
+        const names = []
+        if (networkStatus.ssid)
+            names.push(networkStatus.ssid)
+        if (networkStatus.usb_connected)
+            names.push("USB")
+        let text = names.length > 0 ? "Network: " + names.join(", ") : "Offline"
+        const metered = (networkStatus.metered || []).filter(function(entry) {
+            return names.indexOf(entry.network) >= 0 || (entry.network === "usb" && networkStatus.usb_connected)
+        })
+        metered.forEach(function(entry) {
+            text += ", " + Math.round(entry.used_bytes / 1048576) + " of " + Math.round(entry.budget_bytes / 1048576) + " MiB used today"
+        })
+        if (networkStatus.sync_blocked)
+            text += " \u2013 " + networkStatus.blocked_reason
+        return text
+    }
+
     function friendlySyncthingState() {
         if (syncthingStatus.available) {
             const version = syncthingStatus.version
-            return version ? `Connected (${version})` : "Connected"
+            const label = connectionStatus && connectionStatus.state === "degraded" ? "Degraded" : "Connected"
+            return version ? `${label} (${version})` : label
         }
+        if (connectionStatus && connectionStatus.state === "connecting")
+            return "Connecting\u2026"
+        if (connectionStatus && connectionStatus.state === "unreachable")
+            return "Unreachable"
         return "Offline"
     }
 
+    function connectionSummary() {
+        if (!connectionStatus || !connectionStatus.reason || connectionStatus.state === "connected")
+            return ""
+        return connectionStatus.reason
+    }
+
     ColumnLayout {
         id: contentColumn
         anchors.fill: parent
@@ -131,6 +193,44 @@ Rectangle {
             }
         }
 
+        Text {
+            Layout.fillWidth: true
+            visible: connectionSummary() !== ""
+            text: connectionSummary()
+            font.pointSize: fs(14)
+            color: "#b34700"
+            wrapMode: Text.WordWrap
+            maximumLineCount: 2
+            elide: Text.ElideRight
+        }
+
+        Text {
+            Layout.fillWidth: true
+            visible: scheduleStatus !== null
+            text: scheduleSummary()
+            font.pointSize: fs(14)
+            color: "#4a5268"
+            wrapMode: Text.WordWrap
+        }
+
+        Text {
+            Layout.fillWidth: true
+            visible: powerSummary() !== ""
+            text: powerSummary()
+            font.pointSize: fs(14)
+            color: powerStatus && powerStatus.sync_blocked ? "#b34700" : "#4a5268"
+            wrapMode: Text.WordWrap
+        }
+
+        Text {
+            Layout.fillWidth: true
+            visible: networkSummary() !== ""
+            text: networkSummary()
+            font.pointSize: fs(14)
+            color: networkStatus && networkStatus.sync_blocked ? "#b34700" : "#4a5268"
+            wrapMode: Text.WordWrap
+        }
+
         Rectangle {
             Layout.fillWidth: true
             height: 2
@@ -199,6 +299,60 @@ Rectangle {
             }
         }
 
+        ColumnLayout {
+            Layout.fillWidth: true
+            spacing: 8
+            visible: (syncthingStatus.warnings || []).length > 0
+
+            RowLayout {
+                Layout.fillWidth: true
+                spacing: 16
+
+                Text {
+                    Layout.fillWidth: true
+                    text: "Syncthing warnings"
+                    font.pointSize: fs(18)
+                    font.bold: true
+                    color: "#8a1f1f"
+                }
+
+                Rectangle {
+                    width: 150
+                    height: 52
+                    radius: 18
+                    color: controlBusy ? "#cfd7eb" : accentColor
+                    opacity: controlBusy ? 0.7 : 1
+                    border.width: 0
+
+                    Text {
+                        anchors.centerIn: parent
+                        text: "Clear"
+                        font.pointSize: fs(16)
+                        font.bold: true
+                        color: "#ffffff"
+                    }
+
+                    MouseArea {
+                        anchors.fill: parent
+                        enabled: !controlBusy
+                        onClicked: card.clearWarningsRequested()
+                    }
+                }
+            }
+
+            Repeater {
+                model: (syncthingStatus.warnings || []).slice(0, 5)
+                delegate: Text {
+                    required property var modelData
+                    Layout.fillWidth: true
+                    text: modelData.message
+                    font.pointSize: fs(14)
+                    color: card.warningColor(modelData.severity)
+                    wrapMode: Text.WordWrap
+                }
+            }
+        }
+
         Text {
             Layout.fillWidth: true
             visible: (installerStatus && installerStatus.installer_disabled) && installerAttentionRequired
@@ -11,8 +11,11 @@ Rectangle {
 
     property real fontScale: 1.0
     property var serviceStatus: ({})
+    property var syncthingStatus: ({})
     property bool controlBusy: false
     property string guiAddress: ""
+    property var guiSecurity: null
+    property var guiAuthSetResult: null
     property var updateCheckResult: null
     property var updateStatus: null
     property int updateRestartCountdown: 0
@@ -21,6 +24,7 @@ Rectangle {
     signal closeRequested()
     signal autostartToggleRequested(bool enable)
     signal guiAddressToggleRequested(string address)
+    signal guiAuthSetRequested(string user, string password, bool enableTls)
     signal checkForUpdatesRequested()
     signal downloadUpdateRequested()
     signal restartRequested()
@@ -38,6 +42,46 @@ Rectangle {
         return guiAddress.startsWith("0.0.0.0:")
     }
 
+    function guiSecuritySummary() {
+        if (!guiSecurity)
+            return "Login status unavailable while Syncthing is unreachable"
+        const login = guiSecurity.auth_configured
+            ? `Login required as ${guiSecurity.user}`
+            : "No login set: anyone who can reach the web UI can change Syncthing"
+        return login + (guiSecurity.tls ? " · HTTPS" : " · plain HTTP")
+    }
+
+    function canSaveGuiAuth() {
+        return !controlBusy && !!guiSecurity
+            && guiUserField.text.trim() !== "" && guiPasswordField.text !== ""
+    }
+
+    function connectivityComponents() {
+        const connectivity = syncthingStatus.connectivity || {}
+        return connectivity.components || []
+    }
+
+    function connectivityLabel(component) {
+        switch (component.kind) {
+        case "listener":
+            return "Listener"
+        case "relay":
+            return "Relay"
+        case "global_discovery":
+            return "Global discovery"
+        case "local_discovery":
+            return "Local discovery"
+        default:
+            return component.kind
+        }
+    }
+
+    function connectivitySummary() {
+        const connectivity = syncthingStatus.connectivity || {}
+        const peers = (connectivity.discovered_peers || []).length
+        return peers === 1 ? "1 device found via discovery" : `${peers} devices found via discovery`
+    }
+
     function isRestartPending() {
         return updateStatus && updateStatus.pending_restart
     }
@@ -284,6 +328,177 @@ Rectangle {
                     color: "#5e667d"
                 }
 
+                ColumnLayout {
+                    Layout.fillWidth: true
+                    spacing: 12
+
+                    Text {
+                        text: "Web UI Login"
+                        font.pointSize: fs(22)
+                        font.bold: true
+                        color: "#08122e"
+                    }
+
+                    Text {
+                        text: guiSecuritySummary()
+                        font.pointSize: fs(16)
+                        color: guiSecurity && !guiSecurity.auth_configured ? "#a80c0c" : "#1f2538"
+                        wrapMode: Text.WordWrap
+                        Layout.fillWidth: true
+                    }
+
+                    RowLayout {
+                        Layout.fillWidth: true
+                        spacing: 20
+
+                        TextField {
+                            id: guiUserField
+                            Layout.fillWidth: true
+                            placeholderText: "User"
+                            text: guiSecurity && guiSecurity.user ? guiSecurity.user : ""
+                            font.pointSize: fs(18)
+                            inputMethodHints: Qt.ImhNoAutoUppercase | Qt.ImhNoPredictiveText
+                        }
+
+                        TextField {
+                            id: guiPasswordField
+                            Layout.fillWidth: true
+                            placeholderText: "New password"
+                            echoMode: TextInput.Password
+                            font.pointSize: fs(18)
+                            inputMethodHints: Qt.ImhSensitiveData | Qt.ImhNoPredictiveText
+                        }
+                    }
+
+                    RowLayout {
+                        Layout.fillWidth: true
+                        spacing: 30
+
+                        Text {
+                            text: "Serve the web UI over HTTPS"
+                            font.pointSize: fs(16)
+                            color: "#1f2538"
+                            wrapMode: Text.WordWrap
+                            Layout.fillWidth: true
+                        }
+
+                        Switch {
+                            id: guiTlsSwitch
+                            checked: !!(guiSecurity && guiSecurity.tls)
+                            enabled: !controlBusy && !(guiSecurity && guiSecurity.tls)
+                            scale: 2.2
+                            Layout.alignment: Qt.AlignVCenter
+                            Layout.rightMargin: 30
+                        }
+
+                        Button {
+                            text: "Save"
+                            font.pointSize: fs(20)
+                            enabled: canSaveGuiAuth()
+                            Layout.alignment: Qt.AlignVCenter
+
+                            contentItem: Text {
+                                text: parent.text
+                                font: parent.font
+                                color: parent.enabled ? "#ffffff" : "#9fa8c4"
+                                horizontalAlignment: Text.AlignHCenter
+                                verticalAlignment: Text.AlignVCenter
+                            }
+
+                            background: Rectangle {
+                                color: !parent.enabled ? "#f5f5f5" : (parent.pressed ? "#0f6cca" : accentColor)
+                                border.color: parent.enabled ? accentColor : "#d6ddeb"
+                                border.width: 2
+                                radius: 16
+                                implicitWidth: 160
+                                implicitHeight: 60
+                            }
+
+                            onClicked: {
+                                overlay.guiAuthSetRequested(guiUserField.text.trim(), guiPasswordField.text, guiTlsSwitch.checked)
+                                guiPasswordField.text = ""
+                            }
+                        }
+                    }
+
+                    Text {
+                        visible: !!guiAuthSetResult
+                        text: guiAuthSetResult ? guiAuthSetResult.message : ""
+                        font.pointSize: fs(16)
+                        color: guiAuthSetResult && !guiAuthSetResult.ok ? "#a80c0c" : "#1f2538"
+                        wrapMode: Text.WordWrap
+                        Layout.fillWidth: true
+                    }
+                }
+
+                Rectangle {
+                    Layout.fillWidth: true
+                    Layout.topMargin: 8
+                    Layout.bottomMargin: 8
+                    height: 2
+                    color: "#5e667d"
+                }
+
+                ColumnLayout {
+                    Layout.fillWidth: true
+                    spacing: 12
+                    visible: !!syncthingStatus.available
+
+                    Text {
+                        text: "Connectivity"
+                        font.pointSize: fs(22)
+                        font.bold: true
+                        color: "#08122e"
+                    }
+
+                    Repeater {
+                        model: connectivityComponents()
+                        delegate: RowLayout {
+                            required property var modelData
+                            Layout.fillWidth: true
+                            spacing: 12
+
+                            Rectangle {
+                                width: 18
+                                height: 18
+                                radius: 9
+                                color: modelData.ok ? "#4caf50" : "#d83b3b"
+                            }
+
+                            Text {
+                                Layout.fillWidth: true
+                                text: `${connectivityLabel(modelData)} · ${modelData.name}`
+                                    + (modelData.ok ? " · OK" : ` · ${modelData.error}`)
+                                font.pointSize: fs(16)
+                                color: modelData.ok ? "#1f2538" : "#a80c0c"
+                                wrapMode: Text.WrapAnywhere
+                            }
+                        }
+                    }
+
+                    Text {
+                        visible: connectivityComponents().length === 0
+                        text: "No listener or discovery status reported"
+                        font.pointSize: fs(16)
+                        color: "#4f566a"
+                    }
+
+                    Text {
+                        text: connectivitySummary()
+                        font.pointSize: fs(16)
+                        color: "#1f2538"
+                    }
+                }
+
+                Rectangle {
+                    Layout.fillWidth: true
+                    Layout.topMargin: 8
+                    Layout.bottomMargin: 8
+                    height: 2
+                    color: "#5e667d"
+                    visible: !!syncthingStatus.available
+                }
+
                 ColumnLayout {
                     Layout.fillWidth: true
                     Layout.leftMargin: 0
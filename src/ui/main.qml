@@ -22,23 +22,81 @@ Rectangle {
     readonly property int msgUpdateCheckRequest: 4
     readonly property int msgUpdateDownloadRequest: 5
     readonly property int msgUpdateRestartRequest: 6
+    readonly property int msgFolderPauseRequest: 7
+    readonly property int msgPeerPauseRequest: 8
+    readonly property int msgRescanRequest: 9
+    readonly property int msgPendingDeviceRequest: 10
+    readonly property int msgPendingFolderRequest: 11
+    readonly property int msgFolderCreateRequest: 12
+    readonly property int msgFolderUpdateRequest: 13
+    readonly property int msgFolderDeleteRequest: 14
+    readonly property int msgDeviceQrRequest: 15
+    readonly property int msgConflictScanRequest: 16
+    readonly property int msgConflictResolveRequest: 17
+    readonly property int msgVersionsListRequest: 18
+    readonly property int msgVersionRestoreRequest: 19
+    readonly property int msgIgnoresGetRequest: 20
+    readonly property int msgIgnoresSetRequest: 21
+    readonly property int msgChangesQueryRequest: 22
+    readonly property int msgSystemLogRequest: 23
+    readonly property int msgSystemErrorsClearRequest: 24
+    readonly property int msgBandwidthGetRequest: 25
+    readonly property int msgBandwidthSetRequest: 26
+    readonly property int msgGuiAuthSetRequest: 27
     readonly property int msgStatusUpdate: 100
     readonly property int msgControlResult: 101
     readonly property int msgInstallStatus: 102
     readonly property int msgGuiAddressResult: 103
     readonly property int msgUpdateCheckResult: 104
     readonly property int msgUpdateDownloadStatus: 105
+    readonly property int msgFolderPauseResult: 106
+    readonly property int msgPeerPauseResult: 107
+    readonly property int msgRescanResult: 108
+    readonly property int msgPendingDeviceResult: 109
+    readonly property int msgPendingFolderResult: 110
+    readonly property int msgFolderManageResult: 111
+    readonly property int msgDeviceQrResult: 112
+    readonly property int msgConflictScanResult: 113
+    readonly property int msgConflictResolveResult: 114
+    readonly property int msgVersionsListResult: 115
+    readonly property int msgVersionRestoreResult: 116
+    readonly property int msgIgnoresResult: 117
+    readonly property int msgIgnoresSetResult: 118
+    readonly property int msgChangesQueryResult: 119
+    readonly property int msgSystemLogResult: 120
+    readonly property int msgSystemErrorsClearResult: 121
+    readonly property int msgBandwidthResult: 122
+    readonly property int msgBandwidthSetResult: 123
+    readonly property int msgGuiAuthSetResult: 124
     readonly property int msgError: 500
 
     property var serviceStatus: ({})
     property var syncthingStatus: ({})
+    property var scheduleStatus: null
+    property var powerStatus: null
+    property var networkStatus: null
+    property var connectionStatus: null
     property var folders: []
     property var peers: []
+    property var pendingDevices: []
+    property var pendingFolders: []
     property bool controlBusy: false
     property var installerStatus: null
     property real fontScale: 1.25
     property bool installerPromptDismissed: false
     property string guiAddress: ""
+    property var rescanResult: null
+    property var deviceQr: null
+    property var conflictFolders: []
+    property var versionsList: null
+    property var folderIgnores: null
+    property var ignoresPreview: null
+    property var changeHistory: null
+    property var systemLog: null
+    property var bandwidthLimits: null
+    property var bandwidthSetResult: null
+    property var guiSecurity: null
+    property var guiAuthSetResult: null
     property var updateCheckResult: null
     property var updateStatus: null
     property int updateRestartCountdown: 0
@@ -64,9 +122,16 @@ Rectangle {
                     const payload = JSON.parse(contents)
                     serviceStatus = payload.systemd || {}
                     syncthingStatus = payload.syncthing || {}
+                    connectionStatus = payload.connection || null
                     folders = payload.folders || []
                     peers = payload.peers || []
+                    pendingDevices = payload.pending_devices || []
+                    pendingFolders = payload.pending_folders || []
                     guiAddress = payload.gui_address || ""
+                    guiSecurity = payload.gui_security || null
+                    scheduleStatus = payload.schedule || null
+                    powerStatus = payload.power || null
+                    networkStatus = payload.network || null
                 } catch (err) {
                     console.warn("Failed to parse backend data", err)
                 }
@@ -120,6 +185,146 @@ Rectangle {
                     console.warn("Update status error", errUpdateStatus)
                 }
                 break
+            case root.msgFolderPauseResult:
+                try {
+                    const folderPauseResult = JSON.parse(contents)
+                } catch (errFolderPause) {
+                    console.warn("Folder pause response error", errFolderPause)
+                }
+                controlBusy = false
+                break
+            case root.msgPeerPauseResult:
+                try {
+                    const peerPauseResult = JSON.parse(contents)
+                } catch (errPeerPause) {
+                    console.warn("Peer pause response error", errPeerPause)
+                }
+                controlBusy = false
+                break
+            case root.msgRescanResult:
+                try {
+                    rescanResult = JSON.parse(contents)
+                } catch (errRescan) {
+                    console.warn("Rescan response error", errRescan)
+                }
+                break
+            case root.msgPendingDeviceResult:
+                try {
+                    const pendingDeviceResult = JSON.parse(contents)
+                } catch (errPendingDevice) {
+                    console.warn("Pending device response error", errPendingDevice)
+                }
+                controlBusy = false
+                break
+            case root.msgPendingFolderResult:
+                try {
+                    const pendingFolderResult = JSON.parse(contents)
+                } catch (errPendingFolder) {
+                    console.warn("Pending folder response error", errPendingFolder)
+                }
+                controlBusy = false
+                break
+            case root.msgFolderManageResult:
+                try {
+                    const folderManageResult = JSON.parse(contents)
+                } catch (errFolderManage) {
+                    console.warn("Folder management response error", errFolderManage)
+                }
+                controlBusy = false
+                break
+            case root.msgDeviceQrResult:
+                try {
+                    deviceQr = JSON.parse(contents)
+                } catch (errDeviceQr) {
+                    console.warn("Device QR response error", errDeviceQr)
+                }
+                break
+            case root.msgConflictScanResult:
+                try {
+                    const conflictScan = JSON.parse(contents)
+                    conflictFolders = conflictScan.folders || []
+                } catch (errConflictScan) {
+                    console.warn("Conflict scan response error", errConflictScan)
+                }
+                break
+            case root.msgConflictResolveResult:
+                try {
+                    const conflictResolveResult = JSON.parse(contents)
+                } catch (errConflictResolve) {
+                    console.warn("Conflict resolve response error", errConflictResolve)
+                }
+                controlBusy = false
+                break
+            case root.msgVersionsListResult:
+                try {
+                    versionsList = JSON.parse(contents)
+                } catch (errVersionsList) {
+                    console.warn("Versions list response error", errVersionsList)
+                }
+                break
+            case root.msgVersionRestoreResult:
+                try {
+                    const versionRestoreResult = JSON.parse(contents)
+                } catch (errVersionRestore) {
+                    console.warn("Version restore response error", errVersionRestore)
+                }
+                controlBusy = false
+                break
+            case root.msgIgnoresResult:
+                try {
+                    folderIgnores = JSON.parse(contents)
+                } catch (errIgnores) {
+                    console.warn("Ignores response error", errIgnores)
+                }
+                break
+            case root.msgIgnoresSetResult:
+                try {
+                    ignoresPreview = JSON.parse(contents)
+                } catch (errIgnoresSet) {
+                    console.warn("Ignores update response error", errIgnoresSet)
+                }
+                controlBusy = false
+                break
+            case root.msgChangesQueryResult:
+                try {
+                    changeHistory = JSON.parse(contents)
+                } catch (errChanges) {
+                    console.warn("Changes response error", errChanges)
+                }
+                break
+            case root.msgSystemLogResult:
+                try {
+                    systemLog = JSON.parse(contents)
+                } catch (errLog) {
+                    console.warn("System log response error", errLog)
+                }
+                break
+            case root.msgSystemErrorsClearResult:
+                controlBusy = false
+                break
+            case root.msgBandwidthResult:
+                try {
+                    bandwidthLimits = JSON.parse(contents)
+                } catch (errBandwidth) {
+                    console.warn("Bandwidth response error", errBandwidth)
+                }
+                break
+            case root.msgBandwidthSetResult:
+                try {
+                    bandwidthSetResult = JSON.parse(contents)
+                } catch (errBandwidthSet) {
+                    console.warn("Bandwidth update response error", errBandwidthSet)
+                }
+                controlBusy = false
+                break
+            case root.msgGuiAuthSetResult:
+                try {
+                    guiAuthSetResult = JSON.parse(contents)
+                } catch (errGuiAuth) {
+                    console.warn("GUI login response error", errGuiAuth)
+                }
+                controlBusy = false
+                break
             case root.msgError:
                 try {
                     const errorPayload = JSON.parse(contents)
@@ -169,6 +374,186 @@ Rectangle {
         backend.sendMessage(msgGuiAddressToggle, JSON.stringify({ address: address }))
     }
 
+    function setFolderPaused(folderId, paused) {
+        if (controlBusy)
+            return
+        controlBusy = true
+        backend.sendMessage(msgFolderPauseRequest, JSON.stringify({ folder_id: folderId, paused: paused }))
+    }
+
+    function setPeerPaused(deviceId, paused) {
+        if (controlBusy)
+            return
+        controlBusy = true
+        backend.sendMessage(msgPeerPauseRequest, JSON.stringify({ device_id: deviceId, paused: paused }))
+    }
+
+    function setAllPeersPaused(paused) {
+        if (controlBusy)
+            return
+        controlBusy = true
+        backend.sendMessage(msgPeerPauseRequest, JSON.stringify({ paused: paused }))
+    }
+
+    function rescanFolder(folderId) {
+        const payload = folderId ? { folder_id: folderId } : {}
+        backend.sendMessage(msgRescanRequest, JSON.stringify(payload))
+    }
+
+    function acceptPendingDevice(deviceId, name) {
+        if (controlBusy)
+            return
+        controlBusy = true
+        backend.sendMessage(msgPendingDeviceRequest, JSON.stringify({ device_id: deviceId, action: "accept", name: name }))
+    }
+
+    function dismissPendingDevice(deviceId) {
+        if (controlBusy)
+            return
+        controlBusy = true
+        backend.sendMessage(msgPendingDeviceRequest, JSON.stringify({ device_id: deviceId, action: "dismiss" }))
+    }
+
+    function acceptPendingFolder(folderId, path) {
+        if (controlBusy)
+            return
+        controlBusy = true
+        backend.sendMessage(msgPendingFolderRequest, JSON.stringify({ folder_id: folderId, action: "accept", path: path }))
+    }
+
+    function ignorePendingFolder(folderId) {
+        if (controlBusy)
+            return
+        controlBusy = true
+        backend.sendMessage(msgPendingFolderRequest, JSON.stringify({ folder_id: folderId, action: "ignore" }))
+    }
+
+    function createFolder(folder) {
+        if (controlBusy)
+            return
+        controlBusy = true
+        backend.sendMessage(msgFolderCreateRequest, JSON.stringify(folder))
+    }
+
+    function updateFolder(folder) {
+        if (controlBusy)
+            return
+        controlBusy = true
+        backend.sendMessage(msgFolderUpdateRequest, JSON.stringify(folder))
+    }
+
+    function deleteFolder(folderId) {
+        if (controlBusy)
+            return
+        controlBusy = true
+        backend.sendMessage(msgFolderDeleteRequest, JSON.stringify({ folder_id: folderId }))
+    }
+
+    function requestDeviceQr(includeGuiUrl) {
+        backend.sendMessage(msgDeviceQrRequest, JSON.stringify({ include_gui_url: !!includeGuiUrl }))
+    }
+
+    function scanConflicts(folderId) {
+        backend.sendMessage(msgConflictScanRequest, JSON.stringify({ folder_id: folderId || null }))
+    }
+
+    function resolveConflict(folderId, path, keep) {
+        if (controlBusy)
+            return
+        controlBusy = true
+        backend.sendMessage(msgConflictResolveRequest, JSON.stringify({ folder_id: folderId, path: path, keep: keep }))
+    }
+
+    function listVersions(folderId, prefix) {
+        backend.sendMessage(msgVersionsListRequest, JSON.stringify({ folder_id: folderId, prefix: prefix || null }))
+    }
+
+    function restoreVersion(folderId, path, versionTime) {
+        if (controlBusy)
+            return
+        controlBusy = true
+        backend.sendMessage(msgVersionRestoreRequest, JSON.stringify({ folder_id: folderId, path: path, version_time: versionTime }))
+    }
+
+    function requestIgnores(folderId) {
+        backend.sendMessage(msgIgnoresGetRequest, JSON.stringify({ folder_id: folderId }))
+    }
+
+    function previewIgnores(folderId, patterns) {
+        if (controlBusy)
+            return
+        controlBusy = true
+        backend.sendMessage(msgIgnoresSetRequest, JSON.stringify({ folder_id: folderId, patterns: patterns, dry_run: true }))
+    }
+
+    function saveIgnores(folderId, patterns) {
+        if (controlBusy)
+            return
+        controlBusy = true
+        backend.sendMessage(msgIgnoresSetRequest, JSON.stringify({ folder_id: folderId, patterns: patterns, dry_run: false }))
+    }
+
+    function queryChanges(folderId, deviceId, page) {
+        var payload = { page: page || 1 }
+        if (folderId)
+            payload.folder_id = folderId
+        if (deviceId)
+            payload.device_id = deviceId
+        backend.sendMessage(msgChangesQueryRequest, JSON.stringify(payload))
+    }
+
+    function queryChangesToday(folderId, deviceId, page) {
+        var midnight = new Date()
+        midnight.setHours(0, 0, 0, 0)
+        var payload = { page: page || 1, since: midnight.toISOString() }
+        if (folderId)
+            payload.folder_id = folderId
+        if (deviceId)
+            payload.device_id = deviceId
+        backend.sendMessage(msgChangesQueryRequest, JSON.stringify(payload))
+    }
+
+    function requestSystemLog() {
+        backend.sendMessage(msgSystemLogRequest, JSON.stringify({}))
+    }
+
+    function clearSystemWarnings() {
+        if (controlBusy)
+            return
+        controlBusy = true
+        backend.sendMessage(msgSystemErrorsClearRequest, JSON.stringify({}))
+    }
+
+    function requestBandwidthLimits() {
+        backend.sendMessage(msgBandwidthGetRequest, JSON.stringify({}))
+    }
+
+    function setBandwidthLimits(deviceId, maxSendKbps, maxRecvKbps) {
+        if (controlBusy)
+            return
+        controlBusy = true
+        var payload = {}
+        if (deviceId)
+            payload.device_id = deviceId
+        if (maxSendKbps !== undefined && maxSendKbps !== null)
+            payload.max_send_kbps = maxSendKbps
+        if (maxRecvKbps !== undefined && maxRecvKbps !== null)
+            payload.max_recv_kbps = maxRecvKbps
+        backend.sendMessage(msgBandwidthSetRequest, JSON.stringify(payload))
+    }
+
+    function setGuiAuth(user, password, enableTls) {
+        if (controlBusy)
+            return
+        controlBusy = true
+        guiAuthSetResult = null
+        backend.sendMessage(msgGuiAuthSetRequest, JSON.stringify({
+            user: user,
+            password: password,
+            enable_tls: !!enableTls
+        }))
+    }
+
     function checkForUpdates() {
         backend.sendMessage(msgUpdateCheckRequest, JSON.stringify({}))
     }
@@ -223,6 +608,10 @@ Rectangle {
                 fontScale: root.fontScale
                 serviceStatus: root.serviceStatus
                 syncthingStatus: root.syncthingStatus
+                scheduleStatus: root.scheduleStatus
+                powerStatus: root.powerStatus
+                networkStatus: root.networkStatus
+                connectionStatus: root.connectionStatus
                 controlBusy: root.controlBusy
                 installerStatus: root.installerStatus
                 installerAttentionRequired: root.installerNeedsAttention()
@@ -231,6 +620,7 @@ Rectangle {
 
                 onControlRequested: controlService(action)
                 onSettingsRequested: settingsOverlay.show()
+                onClearWarningsRequested: clearSystemWarnings()
             }
 
             FolderPeersPanel {
@@ -267,8 +657,11 @@ Rectangle {
         fontScale: root.fontScale
         accentColor: root.accentColor
         serviceStatus: root.serviceStatus
+        syncthingStatus: root.syncthingStatus
         controlBusy: root.controlBusy
         guiAddress: root.guiAddress
+        guiSecurity: root.guiSecurity
+        guiAuthSetResult: root.guiAuthSetResult
         updateCheckResult: root.updateCheckResult
         updateStatus: root.updateStatus
         updateRestartCountdown: root.updateRestartCountdown
@@ -283,6 +676,10 @@ Rectangle {
             toggleGuiAddress(address)
         }
 
+        onGuiAuthSetRequested: function(user, password, enableTls) {
+            setGuiAuth(user, password, enableTls)
+        }
+
         onCheckForUpdatesRequested: function() {
             checkForUpdates()
         }